@@ -7,10 +7,14 @@
 // discretion. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use crate::models::common::{Zone, ZoneID, Zones};
+use crate::adapters::dggrid::reader::{AigenReader, DggridZones};
+use crate::models::common::{
+    EARTH_RADIUS_KM, PointBinning, ResolutionInfo, Zone, ZoneID, ZoneQueryOptions, Zones,
+};
 use core::f64;
-use geo::geometry::{LineString, Point, Polygon};
+use geo::Point;
 use rand::distributions::{Alphanumeric, DistString};
+use std::collections::BTreeMap;
 use std::fs;
 use std::fs::File;
 use std::io::{self, BufRead, Write};
@@ -18,15 +22,7 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::debug;
 
-pub const DENSIFICATION: u8 = 50; // DGGRID option
-
-#[derive(Debug)]
-pub struct IdArray {
-    pub id: Option<String>,
-    pub arr: Option<Vec<String>>,
-}
-
-pub fn dggrid_setup(workdir: &PathBuf) -> (PathBuf, PathBuf, PathBuf, PathBuf, PathBuf, PathBuf) {
+pub fn dggrid_setup(workdir: &Path) -> (PathBuf, PathBuf, PathBuf, PathBuf, PathBuf, PathBuf) {
     let code = Alphanumeric.sample_string(&mut rand::thread_rng(), 16);
     let meta_path = workdir.join(&code).with_extension("meta"); // metafile
     let aigen_path = workdir.join(&code).with_extension("gen"); // AIGEN
@@ -45,135 +41,295 @@ pub fn dggrid_setup(workdir: &PathBuf) -> (PathBuf, PathBuf, PathBuf, PathBuf, P
 }
 
 pub fn dggrid_metafile(
-    metafile: &PathBuf,
+    metafile: &Path,
     depth: &u8,
-    cell_output_file_name: &PathBuf,
-    children_output_file_name: &PathBuf,
-    neighbor_output_file_name: &PathBuf,
-    densify: bool,
+    cell_output_file_name: &Path,
+    children_output_file_name: &Path,
+    neighbor_output_file_name: &Path,
+    options: &ZoneQueryOptions,
 ) -> io::Result<()> {
     debug!("Writing to {:?}", metafile);
     let mut file = fs::File::create(metafile)?;
     writeln!(file, "longitude_wrap_mode UNWRAP_EAST")?;
-    writeln!(file, "cell_output_type AIGEN")?;
     writeln!(file, "unwrap_points FALSE")?;
     writeln!(file, "output_cell_label_type OUTPUT_ADDRESS_TYPE")?;
-    writeln!(file, "precision 9")?;
+    writeln!(file, "precision {}", options.precision)?;
     writeln!(file, "dggs_res_spec {}", depth)?;
 
-    writeln!(
-        file,
-        "cell_output_file_name {}",
-        cell_output_file_name.to_string_lossy().into_owned()
-    )?;
-
-    writeln!(file, "neighbor_output_type TEXT")?;
-    writeln!(
-        file,
-        "neighbor_output_file_name {}",
-        neighbor_output_file_name.to_string_lossy().into_owned()
-    )?;
-    writeln!(file, "children_output_type TEXT")?;
-    writeln!(
-        file,
-        "children_output_file_name {}",
-        children_output_file_name.to_string_lossy().into_owned()
-    )?;
-
-    if densify == true {
-        writeln!(file, "densification {}", DENSIFICATION)?;
+    // Without geometry only the centroids are written, in the same AIGEN file
+    if options.wants_geometry() {
+        writeln!(file, "cell_output_type AIGEN")?;
+        writeln!(
+            file,
+            "cell_output_file_name {}",
+            cell_output_file_name.to_string_lossy()
+        )?;
+    } else {
+        writeln!(file, "cell_output_type NONE")?;
+        writeln!(file, "point_output_type AIGEN")?;
+        writeln!(
+            file,
+            "point_output_file_name {}",
+            cell_output_file_name.to_string_lossy()
+        )?;
+    }
+
+    if options.wants_neighbors() {
+        writeln!(file, "neighbor_output_type TEXT")?;
+        writeln!(
+            file,
+            "neighbor_output_file_name {}",
+            neighbor_output_file_name.to_string_lossy()
+        )?;
+    } else {
+        writeln!(file, "neighbor_output_type NONE")?;
+    }
+
+    if options.wants_children() {
+        writeln!(file, "children_output_type TEXT")?;
+        writeln!(
+            file,
+            "children_output_file_name {}",
+            children_output_file_name.to_string_lossy()
+        )?;
+    } else {
+        writeln!(file, "children_output_type NONE")?;
+    }
+
+    if options.wants_geometry() && options.densification > 0 {
+        writeln!(file, "densification {}", options.densification)?;
     }
 
     Ok(())
 }
-pub fn dggrid_execute(dggrid_path: &PathBuf, meta_path: &PathBuf) {
-    let _ = Command::new(&dggrid_path).arg(&meta_path).output();
+pub fn dggrid_execute(dggrid_path: &Path, meta_path: &Path) {
+    let _ = Command::new(dggrid_path).arg(meta_path).output();
+}
+
+/// Look up the zones at `depth` containing the points in a single TRANSFORM_POINTS
+/// run, `dggs_metafile` appending the DGGS specific settings. DGGRID writes one
+/// address per input point, in order. Fails when DGGRID did not run or its output
+/// could not be read.
+pub fn dggrid_transform_points(
+    dggrid_path: &Path,
+    workdir: &Path,
+    depth: u8,
+    points: &[Point],
+    dggs_metafile: fn(&Path) -> io::Result<()>,
+) -> Result<Vec<Option<ZoneID>>, String> {
+    let (meta_path, output_path, _, _, _, input_path) = dggrid_setup(workdir);
+    let run = || -> io::Result<Vec<Option<ZoneID>>> {
+        let mut input = io::BufWriter::new(File::create(&input_path)?);
+        for point in points {
+            writeln!(input, "{} {}", point.x(), point.y())?;
+        }
+        input.flush()?;
+
+        let mut meta = File::create(&meta_path)?;
+        writeln!(meta, "dggrid_operation TRANSFORM_POINTS")?;
+        writeln!(meta, "dggs_res_spec {}", depth)?;
+        writeln!(meta, "input_file_name {}", input_path.to_string_lossy())?;
+        writeln!(meta, "input_address_type GEO")?;
+        writeln!(meta, "input_delimiter \" \"")?;
+        writeln!(meta, "output_file_name {}", output_path.to_string_lossy())?;
+        writeln!(meta, "output_delimiter \" \"")?;
+        drop(meta);
+        dggs_metafile(&meta_path)?;
+
+        dggrid_execute(dggrid_path, &meta_path);
+        let mut ids: Vec<Option<ZoneID>> = Vec::with_capacity(points.len());
+        for line in read_lines(&output_path)? {
+            let line = line?;
+            ids.push(
+                line.split_whitespace()
+                    .next()
+                    .and_then(|address| ZoneID::new(&format!("{:02}{}", depth, address)).ok()),
+            );
+        }
+        ids.resize(points.len(), None);
+        Ok(ids)
+    };
+    let ids = run().map_err(|e| format!("DGGRID point transformation failed: {}", e));
+    let _ = fs::remove_file(&meta_path);
+    let _ = fs::remove_file(&output_path);
+    let _ = fs::remove_file(&input_path);
+    ids
 }
 
+/// Bin points into the zones at `depth` with the `BIN_POINT_VALS` operation of DGGRID, or
+/// `BIN_POINT_PRESENCE` for presence. DGGRID only computes the mean and the count of the values,
+/// so the remaining statistics are computed from the zone ids of `TRANSFORM_POINTS`.
+pub fn dggrid_bin_points(
+    dggrid_path: &Path,
+    workdir: &Path,
+    depth: u8,
+    points: &[Point],
+    values: &[f64],
+    method: PointBinning,
+    dggs_metafile: fn(&Path) -> io::Result<()>,
+) -> Result<BTreeMap<ZoneID, f64>, String> {
+    method.check_values(points.len(), values)?;
+    if matches!(method, PointBinning::Min | PointBinning::Max) {
+        let ids = dggrid_transform_points(dggrid_path, workdir, depth, points, dggs_metafile)?;
+        return method.bin(&ids, values);
+    }
+
+    let (meta_path, output_path, _, _, _, input_path) = dggrid_setup(workdir);
+    let run = || -> io::Result<BTreeMap<ZoneID, f64>> {
+        let mut input = io::BufWriter::new(File::create(&input_path)?);
+        for (i, point) in points.iter().enumerate() {
+            // BIN_POINT_VALS needs a value also when only counting
+            let value = match method {
+                PointBinning::Presence => None,
+                PointBinning::Count => Some(0.0),
+                _ => Some(values[i]),
+            };
+            match value {
+                Some(value) => writeln!(input, "{} {} {}", point.x(), point.y(), value)?,
+                None => writeln!(input, "{} {}", point.x(), point.y())?,
+            }
+        }
+        input.flush()?;
+
+        let mut meta = File::create(&meta_path)?;
+        if method == PointBinning::Presence {
+            writeln!(meta, "dggrid_operation BIN_POINT_PRESENCE")?;
+        } else {
+            writeln!(meta, "dggrid_operation BIN_POINT_VALS")?;
+            writeln!(meta, "bin_method ARITHMETIC_MEAN")?;
+            writeln!(meta, "output_count TRUE")?;
+        }
+        writeln!(meta, "dggs_res_spec {}", depth)?;
+        writeln!(meta, "input_files {}", input_path.to_string_lossy())?;
+        writeln!(meta, "input_delimiter \" \"")?;
+        writeln!(meta, "cell_output_control OUTPUT_OCCUPIED")?;
+        writeln!(meta, "output_file_name {}", output_path.to_string_lossy())?;
+        writeln!(meta, "output_delimiter \" \"")?;
+        drop(meta);
+        dggs_metafile(&meta_path)?;
+
+        dggrid_execute(dggrid_path, &meta_path);
+        parse_bins(io::BufReader::new(File::open(&output_path)?), depth, method)
+    };
+    let bins = run().map_err(|e| format!("DGGRID point binning failed: {}", e));
+    let _ = fs::remove_file(&meta_path);
+    let _ = fs::remove_file(&output_path);
+    let _ = fs::remove_file(&input_path);
+    bins
+}
+
+/// Parses the output of `BIN_POINT_VALS` with `output_count TRUE` or of
+/// `BIN_POINT_PRESENCE` into the statistic of every zone. DGGRID writes one line
+/// per occupied cell with the address, then the point count before the mean, or
+/// the presence vector with one digit per input file. Fails on a line that does
+/// not follow this layout, e.g. a count that is not a positive integer.
+pub fn parse_bins<R: BufRead>(
+    reader: R,
+    depth: u8,
+    method: PointBinning,
+) -> io::Result<BTreeMap<ZoneID, f64>> {
+    let invalid = |line: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid DGGRID binning line: {}", line),
+        )
+    };
+    let mut bins = BTreeMap::new();
+    for line in reader.lines() {
+        let line = line?;
+        let mut fields = line.split_whitespace();
+        let Some(address) = fields.next() else {
+            continue;
+        };
+        let id = ZoneID::new(&format!("{:02}{}", depth, address)).map_err(|_| invalid(&line))?;
+        let value = match method {
+            PointBinning::Presence => {
+                let presence = fields.next().ok_or_else(|| invalid(&line))?;
+                if !presence.contains('1') {
+                    continue;
+                }
+                1.0
+            }
+            _ => {
+                let count = fields
+                    .next()
+                    .and_then(|count| count.parse::<u64>().ok())
+                    .filter(|&count| count > 0)
+                    .ok_or_else(|| invalid(&line))? as f64;
+                let mean: f64 = fields
+                    .next()
+                    .and_then(|mean| mean.parse().ok())
+                    .ok_or_else(|| invalid(&line))?;
+                match method {
+                    PointBinning::Count => count,
+                    PointBinning::Sum => mean * count,
+                    _ => mean,
+                }
+            }
+        };
+        bins.insert(id, value);
+    }
+    Ok(bins)
+}
+
+/// Read all zones produced by a DGGRID run, see [`DggridZones`] for a streaming alternative.
+/// Fails when the output cannot be opened or contains a malformed record.
 pub fn dggrid_parse(
-    aigen_path: &PathBuf,
-    children_path: &PathBuf,
-    neighbor_path: &PathBuf,
+    aigen_path: &Path,
+    children_path: &Path,
+    neighbor_path: &Path,
     depth: &u8,
-) -> Zones {
-    let aigen_data = read_file(&aigen_path);
-    let mut result = parse_aigen(&aigen_data, &depth);
-    let children_data = read_file(&children_path);
-    let children = parse_children(&children_data, &depth);
-    assign_field(&mut result, children, "children");
-
-    let neighbor_data = read_file(&neighbor_path);
-    let neighbors = parse_neighbors(&neighbor_data, &depth);
-    assign_field(&mut result, neighbors, "neighbors");
-    result
+    options: &ZoneQueryOptions,
+) -> io::Result<Zones> {
+    DggridZones::open(aigen_path, children_path, neighbor_path, *depth, options)?.collect()
 }
 
-pub fn parse_aigen(data: &String, depth: &u8) -> Zones {
-    let mut zone_id = ZoneID::default();
-    let mut zones = Zones { zones: Vec::new() };
-
-    let mut raw_coords: Vec<(f64, f64)> = vec![];
-    let mut ply: Polygon;
-    let mut pnt = Point::new(0.0, 0.0);
-    let mut v_count = 0u32;
-
-    // loop over the entire AIGEN file
-    for line in data.lines() {
-        // println!("{:?}", line);
-        let line_parts: Vec<&str> = line.split_whitespace().collect();
-        // The first line of each hexagon is always 3 strings, the first is the ID and the
-        // second two are the center point
-
-        if line_parts.len() == 3 {
-            // For ISEA3H prepend zero-padded depth to the ID
-            let id_str = format!("{:02}{}", depth, line_parts[0]);
-            zone_id = ZoneID::new(&id_str).expect("Cannot accept this id");
-            pnt = Point::new(
-                line_parts[1]
-                    .parse::<f64>()
-                    .expect("cannot parse floating point number"),
-                line_parts[2]
-                    .parse::<f64>()
-                    .expect("cannot parse floating point number"),
-            );
-        // these are coordinate pairs for the region
-        } else if line_parts.len() == 2 {
-            v_count += 1;
-            raw_coords.push((
-                line_parts[0]
-                    .parse::<f64>()
-                    .expect("cannot parse floating point number"),
-                line_parts[1]
-                    .parse::<f64>()
-                    .expect("cannot parse floating point number"),
-            ))
-        // if it just 1 part AND it is END AND if the vertex count is larger than 1
-        } else if line_parts.len() == 1 && line_parts[0] == "END" && v_count > 1 {
-            ply = Polygon::new(LineString::from(raw_coords.clone()), vec![]);
-
-            let zone = Zone {
-                id: zone_id.clone(),
-                region: ply,
-                center: pnt,
-                vertex_count: v_count - 1,
-                children: None,
-                neighbors: None,
-            };
-            zones.zones.push(zone);
+/// Panics with the error of a DGGRID run whose output could not be read, as the zone queries
+/// of the port cannot report it otherwise.
+pub fn expect_output<T>(output: io::Result<T>) -> T {
+    output.unwrap_or_else(|e| panic!("Unable to read DGGRID output: {}", e))
+}
 
-            // reset
-            raw_coords.clear();
-            v_count = 0;
+/// Run DGGRID with the metafile at `paths[0]` and stream its output, see [`DggridZones`]. The
+/// `paths` are the metafile, AIGEN, children, neighbour and bbox paths of [`dggrid_setup`],
+/// which are deleted once the iterator is dropped. Output that cannot be opened, or a
+/// malformed record, panics with [`expect_output`] rather than ending the stream early.
+pub fn dggrid_stream(
+    dggrid_path: &Path,
+    paths: [PathBuf; 5],
+    depth: u8,
+    options: &ZoneQueryOptions,
+) -> Box<dyn Iterator<Item = Zone> + Send> {
+    let [meta_path, aigen_path, children_path, neighbor_path, _] = &paths;
+    print_file(meta_path.clone());
+    dggrid_execute(dggrid_path, meta_path);
+    let zones = DggridZones::open(aigen_path, children_path, neighbor_path, depth, options);
+    if zones.is_err() {
+        for file in &paths {
+            let _ = fs::remove_file(file);
         }
     }
-    zones
+    let zones = expect_output(zones).remove_on_drop(paths.to_vec());
+    Box::new(zones.map(expect_output))
+}
+
+/// Parse the AIGEN cell output of DGGRID.
+pub fn parse_aigen(data: &str, depth: &u8) -> io::Result<Zones> {
+    AigenReader::new(data.as_bytes(), *depth, true).collect()
 }
+
+/// Parse the AIGEN point output of DGGRID, one `id lon lat` line per zone. The
+/// zone regions are left empty.
+pub fn parse_aigen_points(data: &str, depth: &u8) -> io::Result<Zones> {
+    AigenReader::new(data.as_bytes(), *depth, false).collect()
+}
+
 pub fn dggrid_cleanup(
-    meta_path: &PathBuf,
-    aigen_path: &PathBuf,
-    children_path: &PathBuf,
-    neighbor_path: &PathBuf,
-    bbox_path: &PathBuf,
+    meta_path: &Path,
+    aigen_path: &Path,
+    children_path: &Path,
+    neighbor_path: &Path,
+    bbox_path: &Path,
 ) {
     let _ = fs::remove_file(meta_path);
     let _ = fs::remove_file(aigen_path);
@@ -182,82 +338,15 @@ pub fn dggrid_cleanup(
     let _ = fs::remove_file(bbox_path);
 }
 
-pub fn parse_children(data: &String, depth: &u8) -> Vec<IdArray> {
-    data.lines()
-        .filter_map(|line| {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.is_empty() {
-                return None;
-            }
-
-            let id = Some(format!("{:02}{}", depth, parts[0]));
-            let arr = parts
-                .iter()
-                .skip(1)
-                .map(|s| format!("{:02}{}", depth + 1, s))
-                .collect();
-
-            Some(IdArray { id, arr: Some(arr) })
-        })
-        .collect()
-}
-pub fn parse_neighbors(data: &String, depth: &u8) -> Vec<IdArray> {
-    data.lines()
-        .filter_map(|line| {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.is_empty() {
-                return None;
-            }
-
-            let id = Some(format!("{:02}{}", depth, parts[0]));
-            let arr = parts
-                .iter()
-                .skip(1)
-                .map(|s| format!("{:02}{}", depth, s))
-                .collect();
-
-            Some(IdArray { id, arr: Some(arr) })
-        })
-        .collect()
-}
-
-pub fn assign_field(zones: &mut Zones, data: Vec<IdArray>, field: &str) {
-    for item in data {
-        if let Some(ref id_str) = item.id {
-            if let Some(cell) = zones.zones.iter_mut().find(|c| c.id.to_string() == *id_str) {
-                match field {
-                    "children" => cell.children = item.arr.clone(),
-                    "neighbors" => cell.neighbors = item.arr.clone(),
-                    _ => panic!("Unknown field: {}", field),
-                }
-            }
-        }
-    }
-}
-
 pub fn print_file(file: PathBuf) {
     if let Ok(lines) = read_lines(file) {
         // Consumes the iterator, returns an (Optional) String
-        for line in lines.flatten() {
+        for line in lines.map_while(Result::ok) {
             debug!("{}", line);
         }
     }
 }
 
-/// Read aigen file produced by DGGRID
-/// Todo: this is inefficient, use the read_lines function as in print_file
-/// https://doc.rust-lang.org/rust-by-example/std_misc/file/read_lines.html
-pub fn read_file(path: &Path) -> String {
-    let data = fs::read_to_string(path).unwrap_or_else(|e| {
-        panic!(
-            "Unable to read file {}: {}",
-            path.to_str().unwrap_or("unknown"),
-            e
-        )
-    });
-    data
-}
-
 pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where
     P: AsRef<Path>,
@@ -266,7 +355,7 @@ where
     Ok(io::BufReader::new(file).lines())
 }
 
-pub fn bbox_to_aigen(bbox: &Vec<Vec<f64>>, bboxfile: &PathBuf) -> io::Result<()> {
+pub fn bbox_to_aigen(bbox: &[Vec<f64>], bboxfile: &Path) -> io::Result<()> {
     if bbox.len() != 2 || bbox[0].len() != 2 || bbox[1].len() != 2 {
         return Err(io::Error::new(
             io::ErrorKind::InvalidInput,
@@ -301,3 +390,47 @@ pub fn bbox_to_aigen(bbox: &Vec<Vec<f64>>, bboxfile: &PathBuf) -> io::Result<()>
 
     Ok(())
 }
+
+/// The ancestor at `depth` of a zone of a hierarchical 64 bit index such as Z3 and
+/// Z7: the base cell in the 4 leading bits followed by one digit of `digit_bits`
+/// per depth, the digits below the depth of the zone having all their bits set.
+pub fn index_ancestor(
+    zone_id: &ZoneID,
+    depth: u8,
+    digit_bits: u32,
+    max_depth: u8,
+) -> Option<ZoneID> {
+    if zone_id.depth()? < depth || depth > max_depth {
+        return None;
+    }
+    let address = u64::from_str_radix(zone_id.id.get(2..)?, 16).ok()?;
+    let mask = (1u64 << (digit_bits * (max_depth - depth) as u32)) - 1;
+    ZoneID::new(&format!("{:02}{:016x}", depth, address | mask)).ok()
+}
+
+/// Resolution statistics of an icosahedral hexagonal grid with the given aperture, computed with
+/// the same formulas DGGRID uses for `OUTPUT_STATS`. At every depth the grid has 12 pentagons and
+/// `10 * (aperture^depth - 1)` hexagons.
+pub fn isea_resolution_info(aperture: u64, depth: u8) -> ResolutionInfo {
+    let zone_count = aperture
+        .saturating_pow(depth as u32)
+        .saturating_mul(10)
+        .saturating_add(2);
+    let earth_area = 4.0 * f64::consts::PI * EARTH_RADIUS_KM.powi(2);
+    let mean_area = earth_area / zone_count as f64;
+
+    // Centroid distance of regular hexagons with the mean area
+    let spacing = (2.0 * mean_area / 3.0_f64.sqrt()).sqrt();
+
+    // Angular radius of a spherical cap with the mean area
+    let cap_angle = (1.0 - mean_area / (2.0 * f64::consts::PI * EARTH_RADIUS_KM.powi(2))).acos();
+    let cls = 2.0 * EARTH_RADIUS_KM * cap_angle;
+
+    ResolutionInfo {
+        depth,
+        zone_count,
+        mean_area,
+        spacing,
+        cls,
+    }
+}
@@ -9,17 +9,20 @@
 
 use crate::adapters::dggrid::common;
 use crate::adapters::dggrid::dggrid::DggridAdapter;
-use crate::models::common::Zones;
+use crate::models::common::{PointBinning, ResolutionInfo, Zone, ZoneID, ZoneQueryOptions, Zones};
 use crate::ports::dggrs::DggrsPort;
 use core::f64;
 use geo::geometry::Point;
+use std::collections::BTreeMap;
 use std::fs;
 use std::fs::OpenOptions;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::debug;
-pub const CLIP_CELL_DENSIFICATION: u8 = 50; // DGGRID option
+pub const CLIP_CELL_DENSIFICATION: u8 = 50; // DGGRID option, independent of the output densification
+pub const MAX_DEPTH: u8 = 30; // deepest level of the 64 bit Z3 index
 
+#[derive(Default)]
 pub struct Isea3hImpl {
     pub adapter: DggridAdapter,
 }
@@ -31,18 +34,16 @@ impl Isea3hImpl {
             adapter: DggridAdapter::new(executable, workdir),
         }
     }
-}
-
-impl Default for Isea3hImpl {
-    fn default() -> Self {
-        Self {
-            adapter: DggridAdapter::default(),
-        }
-    }
-}
 
-impl DggrsPort for Isea3hImpl {
-    fn zones_from_bbox(&self, depth: u8, densify: bool, bbox: Option<Vec<Vec<f64>>>) -> Zones {
+    /// The zones at `depth` within the cells at `clip_cell_res` whose space separated
+    /// Z3 addresses are given.
+    fn zones_from_clip_cells(
+        &self,
+        depth: u8,
+        clip_cell_res: u8,
+        clip_cell_addresses: &str,
+        options: &ZoneQueryOptions,
+    ) -> Box<dyn Iterator<Item = Zone> + Send> {
         let (meta_path, aigen_path, children_path, neighbor_path, bbox_path, _input_path) =
             common::dggrid_setup(&self.adapter.workdir);
 
@@ -52,43 +53,47 @@ impl DggrsPort for Isea3hImpl {
             &aigen_path.with_extension(""),
             &children_path.with_extension(""),
             &neighbor_path.with_extension(""),
-            densify,
+            options,
         );
 
         let _ = isea3h_metafile(&meta_path);
 
-        if let Some(bbox) = &bbox {
-            let _ = common::bbox_to_aigen(bbox, &bbox_path);
-
-            // Append to metafile
-            let mut meta_file = OpenOptions::new()
-                .append(true)
-                .write(true)
-                .open(&meta_path)
-                .expect("cannot open file");
-
-            let _ = writeln!(meta_file, "clip_subset_type AIGEN");
-            let _ = writeln!(
-                meta_file,
-                "clip_region_files {}",
-                &bbox_path.to_string_lossy()
-            );
-        }
+        // Append to metafile format
+        let mut meta_file = OpenOptions::new()
+            .append(true)
+            .open(&meta_path)
+            .expect("cannot open file");
 
-        common::print_file(meta_path.clone());
-        common::dggrid_execute(&self.adapter.executable, &meta_path);
-        let result = common::dggrid_parse(&aigen_path, &children_path, &neighbor_path, &depth);
-        common::dggrid_cleanup(
-            &meta_path,
-            &aigen_path,
-            &children_path,
-            &neighbor_path,
-            &bbox_path,
+        let _ = writeln!(meta_file, "clip_subset_type zones_from_parent");
+        let _ = writeln!(meta_file, "clip_cell_res {:?}", clip_cell_res);
+        let _ = writeln!(
+            meta_file,
+            "clip_cell_densification {}",
+            CLIP_CELL_DENSIFICATION
         );
-        result
+        let _ = writeln!(meta_file, "clip_cell_addresses \"{}\"", clip_cell_addresses);
+        let _ = writeln!(meta_file, "input_address_type Z3");
+        common::dggrid_stream(
+            &self.adapter.executable,
+            [
+                meta_path,
+                aigen_path,
+                children_path,
+                neighbor_path,
+                bbox_path,
+            ],
+            depth,
+            options,
+        )
     }
 
-    fn zone_from_point(&self, depth: u8, point: Point, densify: bool) -> Zones {
+    /// The zones at `depth` with the given Z3 addresses, in a single run.
+    fn zones_from_addresses(
+        &self,
+        depth: u8,
+        addresses: &[&str],
+        options: &ZoneQueryOptions,
+    ) -> Zones {
         let (meta_path, aigen_path, children_path, neighbor_path, bbox_path, input_path) =
             common::dggrid_setup(&self.adapter.workdir);
 
@@ -98,39 +103,41 @@ impl DggrsPort for Isea3hImpl {
             &aigen_path.with_extension(""),
             &children_path.with_extension(""),
             &neighbor_path.with_extension(""),
-            densify,
+            options,
         );
 
         let _ = isea3h_metafile(&meta_path);
 
-        // Append to metafile
+        // Append to metafile format
         let mut meta_file = OpenOptions::new()
             .append(true)
-            .write(true)
             .open(&meta_path)
             .expect("cannot open file");
 
-        let _ = writeln!(meta_file, "dggrid_operation TRANSFORM_POINTS");
-        let _ = writeln!(meta_file, "input_address_type GEO");
         let _ = writeln!(
             meta_file,
             "input_file_name {}",
             &input_path.to_string_lossy()
         );
 
-        // File with one point
+        // File with one zone per line
         let mut input_file = OpenOptions::new()
             .read(true)
             .write(true)
             .create(true)
+            .truncate(true)
             .open(&input_path)
             .expect("cannot open file");
-        let _ = writeln!(input_file, "{} {}", point.y(), point.x())
-            .expect("Cannot create point input file");
+        for address in addresses {
+            writeln!(input_file, "{}", address).expect("Cannot create zone id input file");
+        }
 
+        let _ = writeln!(meta_file, "dggrid_operation TRANSFORM_POINTS");
+        let _ = writeln!(meta_file, "input_address_type Z3");
         common::print_file(meta_path.clone());
         common::dggrid_execute(&self.adapter.executable, &meta_path);
-        let result = common::dggrid_parse(&aigen_path, &children_path, &neighbor_path, &depth);
+        let result =
+            common::dggrid_parse(&aigen_path, &children_path, &neighbor_path, &depth, options);
         common::dggrid_cleanup(
             &meta_path,
             &aigen_path,
@@ -139,15 +146,26 @@ impl DggrsPort for Isea3hImpl {
             &bbox_path,
         );
         let _ = fs::remove_file(&input_path);
-        result
+        common::expect_output(result)
     }
-    fn zones_from_parent(
+}
+
+impl DggrsPort for Isea3hImpl {
+    fn zones_from_bbox(
         &self,
         depth: u8,
-        parent_zone_id: String, // ToDo: needs validation function
-        // clip_cell_res: u8,
-        densify: bool,
+        options: &ZoneQueryOptions,
+        bbox: Option<Vec<Vec<f64>>>,
     ) -> Zones {
+        self.zones_from_bbox_iter(depth, options, bbox).collect()
+    }
+
+    fn zones_from_bbox_iter(
+        &self,
+        depth: u8,
+        options: &ZoneQueryOptions,
+        bbox: Option<Vec<Vec<f64>>>,
+    ) -> Box<dyn Iterator<Item = Zone> + Send> {
         let (meta_path, aigen_path, children_path, neighbor_path, bbox_path, _input_path) =
             common::dggrid_setup(&self.adapter.workdir);
 
@@ -157,73 +175,65 @@ impl DggrsPort for Isea3hImpl {
             &aigen_path.with_extension(""),
             &children_path.with_extension(""),
             &neighbor_path.with_extension(""),
-            densify,
+            options,
         );
 
         let _ = isea3h_metafile(&meta_path);
 
-        // Append to metafile format
-        let mut meta_file = OpenOptions::new()
-            .append(true)
-            .write(true)
-            .open(&meta_path)
-            .expect("cannot open file");
+        if let Some(bbox) = &bbox {
+            let _ = common::bbox_to_aigen(bbox, &bbox_path);
 
-        let clip_cell_res = extract_res_from_cellid(&parent_zone_id, "ISEA3H").unwrap();
+            // Append to metafile
+            let mut meta_file = OpenOptions::new()
+                .append(true)
+                .open(&meta_path)
+                .expect("cannot open file");
 
-        let clip_cell_address = &parent_zone_id[2..]; // strip first two characters. ToDo: can we get the res from the index itself?
+            let _ = writeln!(meta_file, "clip_subset_type AIGEN");
+            let _ = writeln!(
+                meta_file,
+                "clip_region_files {}",
+                &bbox_path.to_string_lossy()
+            );
+        }
 
-        let _ = writeln!(meta_file, "clip_subset_type zones_from_parent");
-        let _ = writeln!(meta_file, "clip_cell_res {:?}", clip_cell_res);
-        let _ = writeln!(
-            meta_file,
-            "clip_cell_densification {}",
-            CLIP_CELL_DENSIFICATION
-        );
-        let _ = writeln!(meta_file, "clip_cell_addresses \"{}\"", clip_cell_address);
-        let _ = writeln!(meta_file, "input_address_type Z3");
-        common::print_file(meta_path.clone());
-        common::dggrid_execute(&self.adapter.executable, &meta_path);
-        let result = common::dggrid_parse(&aigen_path, &children_path, &neighbor_path, &depth);
-        common::dggrid_cleanup(
-            &meta_path,
-            &aigen_path,
-            &children_path,
-            &neighbor_path,
-            &bbox_path,
-        );
-        result
+        common::dggrid_stream(
+            &self.adapter.executable,
+            [
+                meta_path,
+                aigen_path,
+                children_path,
+                neighbor_path,
+                bbox_path,
+            ],
+            depth,
+            options,
+        )
     }
-    fn zone_from_id(
-        &self,
-        zone_id: String, // ToDo: needs validation function
-        densify: bool,
-    ) -> Zones {
+
+    fn zone_from_point(&self, depth: u8, point: Point, options: &ZoneQueryOptions) -> Zones {
         let (meta_path, aigen_path, children_path, neighbor_path, bbox_path, input_path) =
             common::dggrid_setup(&self.adapter.workdir);
 
-        let clip_cell_res = extract_res_from_cellid(&zone_id, "ISEA3H").unwrap();
-        let depth = clip_cell_res;
         let _ = common::dggrid_metafile(
             &meta_path,
             &depth,
             &aigen_path.with_extension(""),
             &children_path.with_extension(""),
             &neighbor_path.with_extension(""),
-            densify,
+            options,
         );
 
         let _ = isea3h_metafile(&meta_path);
 
-        // Append to metafile format
+        // Append to metafile
         let mut meta_file = OpenOptions::new()
             .append(true)
-            .write(true)
             .open(&meta_path)
             .expect("cannot open file");
 
-        let zone = &zone_id[2..]; // strip first two characters. ToDo: only if we attached the res to the front
-
+        let _ = writeln!(meta_file, "dggrid_operation TRANSFORM_POINTS");
+        let _ = writeln!(meta_file, "input_address_type GEO");
         let _ = writeln!(
             meta_file,
             "input_file_name {}",
@@ -235,15 +245,16 @@ impl DggrsPort for Isea3hImpl {
             .read(true)
             .write(true)
             .create(true)
+            .truncate(true)
             .open(&input_path)
             .expect("cannot open file");
-        let _ = writeln!(input_file, "{}", zone).expect("Cannot create zone id input file");
+        writeln!(input_file, "{} {}", point.x(), point.y())
+            .expect("Cannot create point input file");
 
-        let _ = writeln!(meta_file, "dggrid_operation TRANSFORM_POINTS");
-        let _ = writeln!(meta_file, "input_address_type Z3");
         common::print_file(meta_path.clone());
         common::dggrid_execute(&self.adapter.executable, &meta_path);
-        let result = common::dggrid_parse(&aigen_path, &children_path, &neighbor_path, &depth);
+        let result =
+            common::dggrid_parse(&aigen_path, &children_path, &neighbor_path, &depth, options);
         common::dggrid_cleanup(
             &meta_path,
             &aigen_path,
@@ -251,19 +262,132 @@ impl DggrsPort for Isea3hImpl {
             &neighbor_path,
             &bbox_path,
         );
-        result
+        let _ = fs::remove_file(&input_path);
+        common::expect_output(result)
+    }
+    fn zones_from_parent(
+        &self,
+        depth: u8,
+        parent_zone_id: String,
+        options: &ZoneQueryOptions,
+    ) -> Zones {
+        self.zones_from_parent_iter(depth, parent_zone_id, options)
+            .collect()
+    }
+
+    fn zones_from_parent_iter(
+        &self,
+        depth: u8,
+        parent_zone_id: String, // ToDo: needs validation function
+        options: &ZoneQueryOptions,
+    ) -> Box<dyn Iterator<Item = Zone> + Send> {
+        let clip_cell_res = extract_res_from_cellid(&parent_zone_id, "ISEA3H").unwrap();
+        // strip first two characters. ToDo: can we get the res from the index itself?
+        self.zones_from_clip_cells(depth, clip_cell_res, &parent_zone_id[2..], options)
+    }
+
+    fn zones_from_parents(
+        &self,
+        depth: u8,
+        parent_zone_ids: &[ZoneID],
+        options: &ZoneQueryOptions,
+    ) -> Zones {
+        // DGGRID clips to a list of cells as long as they share their depth
+        let mut by_depth: BTreeMap<u8, Vec<&str>> = BTreeMap::new();
+        for id in parent_zone_ids {
+            if let Some(d) = id.depth() {
+                by_depth.entry(d).or_default().push(&id.id[2..]);
+            }
+        }
+        let mut zones: Zones = by_depth
+            .into_iter()
+            .flat_map(|(clip_cell_res, addresses)| {
+                self.zones_from_clip_cells(depth, clip_cell_res, &addresses.join(" "), options)
+            })
+            .collect();
+        zones.dedup();
+        zones
+    }
+
+    fn zone_ancestor(&self, zone_id: &ZoneID, depth: u8) -> Option<ZoneID> {
+        common::index_ancestor(zone_id, depth, 2, MAX_DEPTH)
+    }
+
+    fn zone_from_id(
+        &self,
+        zone_id: String, // ToDo: needs validation function
+        options: &ZoneQueryOptions,
+    ) -> Zones {
+        let depth = extract_res_from_cellid(&zone_id, "ISEA3H").unwrap();
+        // strip first two characters. ToDo: only if we attached the res to the front
+        self.zones_from_addresses(depth, &[&zone_id[2..]], options)
+    }
+
+    fn zones_from_ids(&self, zone_ids: &[ZoneID], options: &ZoneQueryOptions) -> Zones {
+        // One run per depth, DGGRID transforms a list of addresses at a single depth
+        let mut by_depth: BTreeMap<u8, Vec<&str>> = BTreeMap::new();
+        for id in zone_ids {
+            if let Some(depth) = id.depth() {
+                by_depth.entry(depth).or_default().push(&id.id[2..]);
+            }
+        }
+        let mut zones: Zones = by_depth
+            .into_iter()
+            .flat_map(|(depth, addresses)| self.zones_from_addresses(depth, &addresses, options))
+            .collect();
+        zones.dedup();
+        zones
+    }
+
+    fn zone_ids_from_points(
+        &self,
+        depth: u8,
+        points: &[Point],
+    ) -> Result<Vec<Option<ZoneID>>, String> {
+        common::dggrid_transform_points(
+            &self.adapter.executable,
+            &self.adapter.workdir,
+            depth,
+            points,
+            isea3h_metafile,
+        )
+    }
+
+    fn bin_points(
+        &self,
+        depth: u8,
+        points: &[Point],
+        values: &[f64],
+        method: PointBinning,
+    ) -> Result<BTreeMap<ZoneID, f64>, String> {
+        common::dggrid_bin_points(
+            &self.adapter.executable,
+            &self.adapter.workdir,
+            depth,
+            points,
+            values,
+            method,
+            isea3h_metafile,
+        )
+    }
+
+    fn max_depth(&self) -> u8 {
+        MAX_DEPTH
+    }
+
+    fn resolution_info(&self, depth: u8) -> ResolutionInfo {
+        common::isea_resolution_info(3, depth)
     }
 }
 
-pub fn isea3h_metafile(meta_path: &PathBuf) -> io::Result<()> {
+pub fn isea3h_metafile(meta_path: &Path) -> io::Result<()> {
     debug!("Writing to {:?}", meta_path);
     // Append to metafile format
     let mut meta_file = OpenOptions::new()
         .append(true)
-        .write(true)
-        .open(&meta_path)
+        .open(meta_path)
         .expect("cannot open file");
-    writeln!(meta_file, "dggs_type {}", "ISEA3H")?;
+    writeln!(meta_file, "dggs_type ISEA3H")?;
     writeln!(meta_file, "dggs_aperture 3")?;
     writeln!(meta_file, "output_address_type Z3")?;
 
@@ -8,6 +8,8 @@
 // except according to those terms.
 
 pub mod common;
+#[allow(clippy::module_inception)]
 pub mod dggrid;
 pub mod igeo7;
 pub mod isea3h;
+pub mod reader;
@@ -0,0 +1,260 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::models::common::{Zone, ZoneID, ZoneQueryOptions};
+use geo::geometry::{LineString, Point, Polygon};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// Parses zones one at a time out of DGGRID AIGEN output. Cell output consists
+/// of an `id lon lat` line, the vertices and an `END` line per zone, point output
+/// of a single `id lon lat` line per zone. A read error or a malformed line is
+/// yielded as an error, after which the reader stops.
+pub struct AigenReader<R: BufRead> {
+    lines: io::Lines<R>,
+    depth: u8,
+    geometry: bool,
+    failed: bool,
+}
+
+impl<R: BufRead> AigenReader<R> {
+    pub fn new(reader: R, depth: u8, geometry: bool) -> Self {
+        Self {
+            lines: reader.lines(),
+            depth,
+            geometry,
+            failed: false,
+        }
+    }
+
+    fn next_zone(&mut self) -> io::Result<Option<Zone>> {
+        let mut zone_id = ZoneID::default();
+        let mut pnt = Point::new(0.0, 0.0);
+        let mut raw_coords: Vec<(f64, f64)> = vec![];
+
+        for line in self.lines.by_ref() {
+            let line = line?;
+            let line_parts: Vec<&str> = line.split_whitespace().collect();
+
+            // The first line of each zone is always 3 strings, the first is the ID and the
+            // second two are the center point
+            if line_parts.len() == 3 {
+                zone_id = parse_id(line_parts[0], self.depth)?;
+                pnt = Point::new(parse_f64(line_parts[1])?, parse_f64(line_parts[2])?);
+                raw_coords.clear();
+
+                if !self.geometry {
+                    return Ok(Some(Zone {
+                        id: zone_id,
+                        region: Polygon::new(LineString::new(vec![]), vec![]),
+                        center: pnt,
+                        vertex_count: 0,
+                        children: vec![],
+                        neighbors: vec![],
+                    }));
+                }
+            // these are coordinate pairs for the region
+            } else if line_parts.len() == 2 {
+                raw_coords.push((parse_f64(line_parts[0])?, parse_f64(line_parts[1])?));
+            // if it just 1 part AND it is END AND if the vertex count is larger than 1
+            } else if line_parts.len() == 1 && line_parts[0] == "END" && raw_coords.len() > 1 {
+                let vertex_count = raw_coords.len() as u32 - 1;
+                return Ok(Some(Zone {
+                    id: zone_id,
+                    region: Polygon::new(LineString::from(raw_coords), vec![]),
+                    center: pnt,
+                    vertex_count,
+                    children: vec![],
+                    neighbors: vec![],
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<R: BufRead> Iterator for AigenReader<R> {
+    type Item = io::Result<Zone>;
+
+    fn next(&mut self) -> Option<io::Result<Zone>> {
+        if self.failed {
+            return None;
+        }
+        let zone = self.next_zone();
+        self.failed = zone.is_err();
+        zone.transpose()
+    }
+}
+
+/// Reads a DGGRID children or neighbours text file in step with the cell output.
+/// DGGRID writes the cells, children and neighbours in the same order, so only
+/// the current line is held in memory.
+pub struct RelationReader<R: BufRead> {
+    lines: io::Lines<R>,
+    depth: u8,
+    related_depth: u8,
+    /// The next line, parsed, not yet matched with a cell.
+    pending: Option<(ZoneID, Vec<ZoneID>)>,
+}
+
+impl<R: BufRead> RelationReader<R> {
+    /// Reads lines listing zones at `depth` related to zones at `related_depth`.
+    pub fn new(reader: R, depth: u8, related_depth: u8) -> Self {
+        Self {
+            lines: reader.lines(),
+            depth,
+            related_depth,
+            pending: None,
+        }
+    }
+
+    /// The ids related to `zone_id`, which must be requested in the order of the
+    /// file. A zone missing from the file has no related ids, and the line read
+    /// ahead is kept for the next zone.
+    pub fn get(&mut self, zone_id: &ZoneID) -> io::Result<Vec<ZoneID>> {
+        if self.pending.is_none() {
+            self.pending = self.read_line()?;
+        }
+        match self.pending.take() {
+            Some((id, related)) if id == *zone_id => Ok(related),
+            other => {
+                self.pending = other;
+                Ok(vec![])
+            }
+        }
+    }
+
+    fn read_line(&mut self) -> io::Result<Option<(ZoneID, Vec<ZoneID>)>> {
+        for line in self.lines.by_ref() {
+            let line = line?;
+            let mut parts = line.split_whitespace();
+            let Some(id) = parts.next() else {
+                continue;
+            };
+            let related = parts
+                .map(|s| parse_id(s, self.related_depth))
+                .collect::<io::Result<_>>()?;
+            return Ok(Some((parse_id(id, self.depth)?, related)));
+        }
+        Ok(None)
+    }
+}
+
+/// Streams the zones produced by a DGGRID run, merging in the children and
+/// neighbours with a [`RelationReader`] each, so the memory use does not grow
+/// with the number of zones. The DGGRID files handed over with
+/// [`DggridZones::remove_on_drop`] are deleted once the iterator is dropped.
+pub struct DggridZones {
+    cells: AigenReader<BufReader<File>>,
+    children: Option<RelationReader<BufReader<File>>>,
+    neighbors: Option<RelationReader<BufReader<File>>>,
+    files: Vec<PathBuf>,
+}
+
+impl DggridZones {
+    pub fn open(
+        aigen_path: &Path,
+        children_path: &Path,
+        neighbor_path: &Path,
+        depth: u8,
+        options: &ZoneQueryOptions,
+    ) -> io::Result<Self> {
+        let cells = AigenReader::new(
+            BufReader::new(File::open(aigen_path)?),
+            depth,
+            options.wants_geometry(),
+        );
+
+        let children = if options.wants_children() {
+            Some(RelationReader::new(
+                BufReader::new(File::open(children_path)?),
+                depth,
+                depth + 1,
+            ))
+        } else {
+            None
+        };
+
+        let neighbors = if options.wants_neighbors() {
+            Some(RelationReader::new(
+                BufReader::new(File::open(neighbor_path)?),
+                depth,
+                depth,
+            ))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            cells,
+            children,
+            neighbors,
+            files: vec![],
+        })
+    }
+
+    /// Delete the given files when the iterator is dropped.
+    pub fn remove_on_drop(mut self, files: Vec<PathBuf>) -> Self {
+        self.files = files;
+        self
+    }
+}
+
+/// Yields zones until the output ends, or a read error or a malformed line,
+/// which is yielded as an error and ends the iteration.
+impl Iterator for DggridZones {
+    type Item = io::Result<Zone>;
+
+    fn next(&mut self) -> Option<io::Result<Zone>> {
+        let mut zone = match self.cells.next()? {
+            Ok(zone) => zone,
+            Err(e) => return Some(Err(e)),
+        };
+        let relations = [
+            (self.children.as_mut(), &mut zone.children),
+            (self.neighbors.as_mut(), &mut zone.neighbors),
+        ];
+        for (reader, related) in relations {
+            if let Some(reader) = reader {
+                match reader.get(&zone.id) {
+                    Ok(ids) => *related = ids,
+                    Err(e) => {
+                        self.cells.failed = true;
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+        Some(Ok(zone))
+    }
+}
+
+impl Drop for DggridZones {
+    fn drop(&mut self) {
+        for file in &self.files {
+            let _ = fs::remove_file(file);
+        }
+    }
+}
+
+fn parse_f64(s: &str) -> io::Result<f64> {
+    s.parse::<f64>().map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid coordinate {}: {}", s, e),
+        )
+    })
+}
+
+/// Prepends the zero-padded depth to a DGGRID address.
+fn parse_id(address: &str, depth: u8) -> io::Result<ZoneID> {
+    ZoneID::new(&format!("{:02}{}", depth, address))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", address, e)))
+}
@@ -0,0 +1,184 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::{Coverage, area_km2, overlaps, zones_in_blocks};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// How the values of the cells within a coarser zone are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Sum of the values, each weighted by the fraction of its cell inside the zone.
+    Sum,
+    /// Mean of the values, weighted by the fraction of their cell inside the zone.
+    Mean,
+    /// Mean of the values, weighted by the area of their cell inside the zone.
+    AreaWeightedMean,
+    /// Smallest value of the cells overlapping the zone, however little of a
+    /// partially contained cell lies inside it.
+    Min,
+    /// Largest value of the cells overlapping the zone, however little of a
+    /// partially contained cell lies inside it.
+    Max,
+    /// Number of cells with a value, partially contained cells counting by fraction.
+    Count,
+    /// The value covering the largest area of the zone, for categorical values.
+    Majority,
+}
+
+/// A cell of a finer coverage contributing to a zone of a coarser one.
+#[derive(Debug, Clone, Copy)]
+pub struct Contribution<'a, T> {
+    pub value: &'a T,
+    /// Fraction of the cell area inside the coarser zone, 1 for a fully contained cell.
+    pub fraction: f64,
+    /// Area of the cell inside the coarser zone, in km².
+    pub area: f64,
+}
+
+impl<T> Coverage<T> {
+    /// Aggregates the coverage into a coarser one at `depth`, computing every
+    /// coarser zone from the cells contributing to it with `f`. The cells of a
+    /// zone are its sub-zones from `zones_from_parents`, and those of its
+    /// neighbours. With aperture 3 and 7 hexagons a cell may straddle several
+    /// coarser zones, its contributions are then weighted by the area inside each.
+    ///
+    /// The aggregated coverage keeps the blocks when they are coarser than
+    /// `depth`, or uses their ancestors one level above `depth` otherwise.
+    pub fn aggregate_with<U, F>(&self, depth: u8, f: F) -> Result<Coverage<U>, String>
+    where
+        F: Fn(&[Contribution<'_, T>]) -> Option<U>,
+    {
+        if depth >= self.metadata.depth {
+            return Err(format!(
+                "Depth {} is not coarser than the coverage depth {}",
+                depth, self.metadata.depth
+            ));
+        }
+        if depth == 0 {
+            return Err("Cannot aggregate to depth 0, blocks need a coarser depth".to_string());
+        }
+
+        let dggrs = self.dggrs.as_ref();
+        let (block_depth, blocks) = if self.metadata.block_depth < depth {
+            (self.metadata.block_depth, self.zone_ids())
+        } else {
+            let mut blocks = vec![];
+            let mut seen = HashSet::new();
+            for block in &self.blocks {
+                if let Some(id) = dggrs.zone_ancestor(&block.id, depth - 1)
+                    && seen.insert(id.clone())
+                {
+                    blocks.push(id);
+                }
+            }
+            (depth - 1, blocks)
+        };
+
+        let mut target =
+            Coverage::new(&self.metadata.dggrs, self.dggrs.clone(), depth, block_depth)?;
+        for block in &blocks {
+            target.add_block(block)?;
+        }
+        let parents = zones_in_blocks(dggrs, &blocks, depth, |id| target.contains(id));
+        let overlaps = overlaps(dggrs, &parents, self.metadata.depth, |id| {
+            self.get(id).is_some()
+        });
+
+        for (parent, pairs) in parents.iter().zip(&overlaps.pairs) {
+            let contributions: Vec<Contribution<'_, T>> = pairs
+                .iter()
+                .filter_map(|&(i, area)| {
+                    let cell = &overlaps.cells[i];
+                    Some(Contribution {
+                        value: self.get(&cell.id)?,
+                        fraction: (area / area_km2(&cell.region)).min(1.0),
+                        area,
+                    })
+                })
+                .collect();
+
+            if !contributions.is_empty()
+                && let Some(value) = f(&contributions)
+            {
+                target.set(&parent.id, value)?;
+            }
+        }
+        Ok(target)
+    }
+}
+
+impl<T: Clone + Eq + Hash> Coverage<T> {
+    /// Aggregates categorical values into a coarser coverage at `depth`,
+    /// keeping the value covering the largest area of each zone.
+    pub fn majority(&self, depth: u8) -> Result<Coverage<T>, String> {
+        self.aggregate_with(depth, |contributions| {
+            majority_by(contributions, |value| value.clone()).cloned()
+        })
+    }
+}
+
+impl Coverage<f64> {
+    /// Aggregates the coverage into a coarser one at `depth`, see [`Aggregation`]
+    /// for the available methods and [`Coverage::aggregate_with`] for how cells
+    /// contribute to the coarser zones.
+    pub fn aggregate(&self, depth: u8, method: Aggregation) -> Result<Coverage<f64>, String> {
+        self.aggregate_with(depth, |contributions| {
+            let values = contributions.iter().map(|c| *c.value);
+            match method {
+                Aggregation::Sum => Some(contributions.iter().map(|c| c.value * c.fraction).sum()),
+                Aggregation::Mean => weighted_mean(contributions, |c| c.fraction),
+                Aggregation::AreaWeightedMean => weighted_mean(contributions, |c| c.area),
+                Aggregation::Min => values.reduce(f64::min),
+                Aggregation::Max => values.reduce(f64::max),
+                Aggregation::Count => Some(contributions.iter().map(|c| c.fraction).sum()),
+                Aggregation::Majority => {
+                    majority_by(contributions, |value| value.to_bits()).copied()
+                }
+            }
+        })
+    }
+}
+
+fn weighted_mean<F>(contributions: &[Contribution<'_, f64>], weight: F) -> Option<f64>
+where
+    F: Fn(&Contribution<'_, f64>) -> f64,
+{
+    let total: f64 = contributions.iter().map(&weight).sum();
+    if total > 0.0 {
+        Some(
+            contributions
+                .iter()
+                .map(|c| c.value * weight(c))
+                .sum::<f64>()
+                / total,
+        )
+    } else {
+        None
+    }
+}
+
+/// The value with the largest area, ties going to the value met first.
+fn majority_by<'a, T, K, F>(contributions: &[Contribution<'a, T>], key: F) -> Option<&'a T>
+where
+    K: Eq + Hash,
+    F: Fn(&T) -> K,
+{
+    let mut areas: HashMap<K, (usize, &'a T, f64)> = HashMap::new();
+    for (i, contribution) in contributions.iter().enumerate() {
+        areas
+            .entry(key(contribution.value))
+            .or_insert((i, contribution.value, 0.0))
+            .2 += contribution.area;
+    }
+    areas
+        .into_values()
+        .max_by(|a, b| a.2.total_cmp(&b.2).then(b.0.cmp(&a.0)))
+        .map(|(_, value, _)| value)
+}
@@ -0,0 +1,163 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::Coverage;
+use crate::coverage::aggregate::Aggregation;
+use crate::coverage::disaggregate::Disaggregation;
+use std::borrow::Cow;
+
+impl<T> Coverage<T> {
+    /// Applies `f` to the value of every cell. Cells without value stay
+    /// without, as do those for which `f` returns `None`.
+    pub fn map<U, F>(&self, f: F) -> Coverage<U>
+    where
+        F: Fn(&T) -> Option<U>,
+    {
+        let mut target = self.empty_like();
+        for (id, value) in self.iter() {
+            if let Some(value) = f(value) {
+                // The cell exists in the copied layout
+                let _ = target.set(id, value);
+            }
+        }
+        target
+    }
+
+    /// Combines the values of this coverage and `other` cell by cell with `f`.
+    /// Both must be on the same DGGRS and depth. The result has the cells of this
+    /// coverage, without value where either coverage has none.
+    pub fn zip_with<U, V, F>(&self, other: &Coverage<U>, f: F) -> Result<Coverage<V>, String>
+    where
+        F: Fn(&T, &U) -> Option<V>,
+    {
+        self.check_compatible(other)?;
+        if other.resolution() != self.resolution() {
+            return Err(format!(
+                "Cannot combine coverages at depths {} and {}",
+                self.resolution(),
+                other.resolution()
+            ));
+        }
+
+        let mut target = self.empty_like();
+        for (id, value) in self.iter() {
+            if let Some(value) = other.get(id).and_then(|other| f(value, other)) {
+                target.set(id, value)?;
+            }
+        }
+        Ok(target)
+    }
+
+    fn check_compatible<U>(&self, other: &Coverage<U>) -> Result<(), String> {
+        if self.dggrs() != other.dggrs() {
+            return Err(format!(
+                "Cannot combine coverages on {} and {}",
+                self.dggrs(),
+                other.dggrs()
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Coverage<bool> {
+    /// Takes the value of `then` where this coverage is true and of `otherwise`
+    /// where it is false, as `where` in array libraries. All three coverages
+    /// must be on the same DGGRS and depth.
+    pub fn select<T: Clone>(
+        &self,
+        then: &Coverage<T>,
+        otherwise: &Coverage<T>,
+    ) -> Result<Coverage<T>, String> {
+        self.check_compatible(otherwise)?;
+        if otherwise.resolution() != self.resolution() {
+            return Err(format!(
+                "Cannot combine coverages at depths {} and {}",
+                self.resolution(),
+                otherwise.resolution()
+            ));
+        }
+        let mut target =
+            self.zip_with(then, |&condition, value| condition.then(|| value.clone()))?;
+        for (id, condition) in self.iter() {
+            if !condition && let Some(value) = otherwise.get(id) {
+                target.set(id, value.clone())?;
+            }
+        }
+        Ok(target)
+    }
+}
+
+impl Coverage<f64> {
+    /// This coverage at the depth of `other`, aggregating with the mean or
+    /// copying values down the hierarchy. Values are hence treated as intensive
+    /// quantities, align extensive ones explicitly with `aggregate` and
+    /// `disaggregate` beforehand.
+    pub fn align_to<U>(&self, other: &Coverage<U>) -> Result<Cow<'_, Coverage<f64>>, String> {
+        self.check_compatible(other)?;
+        match other.resolution() {
+            depth if depth < self.resolution() => {
+                Ok(Cow::Owned(self.aggregate(depth, Aggregation::Mean)?))
+            }
+            depth if depth > self.resolution() => Ok(Cow::Owned(self.disaggregate(
+                depth,
+                Disaggregation::Copy,
+                None,
+            )?)),
+            _ => Ok(Cow::Borrowed(self)),
+        }
+    }
+
+    /// Combines this coverage with `other` cell by cell, aligning `other` to the
+    /// depth of this coverage first if needed, see [`Coverage::align_to`].
+    pub fn combine<V, F>(&self, other: &Coverage<f64>, f: F) -> Result<Coverage<V>, String>
+    where
+        F: Fn(f64, f64) -> Option<V>,
+    {
+        let other = other.align_to(self)?;
+        self.zip_with(&other, |&a, &b| f(a, b))
+    }
+
+    pub fn add(&self, other: &Coverage<f64>) -> Result<Coverage<f64>, String> {
+        self.combine(other, |a, b| Some(a + b))
+    }
+
+    pub fn sub(&self, other: &Coverage<f64>) -> Result<Coverage<f64>, String> {
+        self.combine(other, |a, b| Some(a - b))
+    }
+
+    pub fn mul(&self, other: &Coverage<f64>) -> Result<Coverage<f64>, String> {
+        self.combine(other, |a, b| Some(a * b))
+    }
+
+    /// Division, leaving the cells divided by zero without value.
+    pub fn div(&self, other: &Coverage<f64>) -> Result<Coverage<f64>, String> {
+        self.combine(other, |a, b| (b != 0.0).then(|| a / b))
+    }
+
+    pub fn gt(&self, other: &Coverage<f64>) -> Result<Coverage<bool>, String> {
+        self.combine(other, |a, b| Some(a > b))
+    }
+
+    pub fn ge(&self, other: &Coverage<f64>) -> Result<Coverage<bool>, String> {
+        self.combine(other, |a, b| Some(a >= b))
+    }
+
+    pub fn lt(&self, other: &Coverage<f64>) -> Result<Coverage<bool>, String> {
+        self.combine(other, |a, b| Some(a < b))
+    }
+
+    pub fn le(&self, other: &Coverage<f64>) -> Result<Coverage<bool>, String> {
+        self.combine(other, |a, b| Some(a <= b))
+    }
+
+    pub fn equals(&self, other: &Coverage<f64>) -> Result<Coverage<bool>, String> {
+        self.combine(other, |a, b| Some(a == b))
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::Coverage;
+use crate::models::common::PointBinning;
+use crate::ports::dggrs::DggrsPort;
+use geo::Point;
+use std::sync::Arc;
+
+impl Coverage<f64> {
+    /// Bins point observations, with one value each, into a coverage at `depth`
+    /// holding `method` of the points in each zone, as hexbinning does. Only the
+    /// zones with points get a value. The blocks at `block_depth` are those
+    /// containing the points. The values are ignored, and may be empty, for
+    /// `Count` and `Presence`.
+    ///
+    /// The binning is done by `DggrsPort::bin_points`, natively by DGGRID for its
+    /// grids and with point to zone lookups otherwise.
+    pub fn from_points(
+        name: &str,
+        dggrs: Arc<dyn DggrsPort>,
+        depth: u8,
+        block_depth: u8,
+        points: &[Point],
+        values: &[f64],
+        method: PointBinning,
+    ) -> Result<Self, String> {
+        method.check_values(points.len(), values)?;
+        let mut coverage = Coverage::new(name, dggrs.clone(), depth, block_depth)?;
+        for block in dggrs
+            .zone_ids_from_points(block_depth, points)?
+            .iter()
+            .flatten()
+        {
+            coverage.add_block(block)?;
+        }
+
+        for (id, value) in dggrs.bin_points(depth, points, values, method)? {
+            // A zone straddling blocks may fall outside those of its points
+            if !coverage.contains(&id)
+                && let Some(block) = dggrs.zone_ancestor(&id, block_depth)
+            {
+                coverage.add_block(&block)?;
+            }
+            coverage.set(&id, value)?;
+        }
+        Ok(coverage)
+    }
+}
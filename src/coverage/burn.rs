@@ -0,0 +1,216 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::{Coverage, area_km2};
+use crate::encoding::antimeridian::split_antimeridian;
+use crate::models::common::{Zone, ZoneID, ZoneQueryOptions};
+use crate::ports::dggrs::DggrsPort;
+use geo::{BooleanOps, BoundingRect, Geometry, Intersects, MultiPolygon, Rect, coord};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Decides which cells a feature is burnt into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BurnRule {
+    /// Cells whose centroid lies inside the feature.
+    Centroid,
+    /// Cells intersecting the feature.
+    Intersects,
+    /// Cells with at least half of their area inside the feature.
+    MajorityArea,
+}
+
+/// Resolves the value of a cell several features are burnt into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    /// The value of the feature with the highest priority, the first one given
+    /// among those with the same priority.
+    Priority,
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// Number of features.
+    Count,
+}
+
+/// A geometry with the value burnt into the cells it covers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Burn {
+    pub geometry: Geometry,
+    pub value: f64,
+    /// Decides between overlapping features with `Conflict::Priority`, 0 by default.
+    pub priority: i32,
+}
+
+impl Burn {
+    pub fn new<G: Into<Geometry>>(geometry: G, value: f64) -> Self {
+        Self {
+            geometry: geometry.into(),
+            value,
+            priority: 0,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// The areal part of the geometry, `None` for points and lines.
+    fn area(&self) -> Option<MultiPolygon> {
+        match &self.geometry {
+            Geometry::Polygon(polygon) => Some(polygon.clone().into()),
+            Geometry::MultiPolygon(polygons) => Some(polygons.clone()),
+            Geometry::Rect(rect) => Some(rect.to_polygon().into()),
+            Geometry::Triangle(triangle) => Some(triangle.to_polygon().into()),
+            _ => None,
+        }
+    }
+
+    /// Whether the feature, whose `area` is computed once beforehand, is burnt
+    /// into the zone following `rule`, `region` being the region of the zone split
+    /// at the antimeridian. Points and lines have no area, so they are burnt into
+    /// every zone they intersect.
+    fn covers(
+        &self,
+        area: Option<&MultiPolygon>,
+        zone: &Zone,
+        region: &MultiPolygon,
+        rule: BurnRule,
+    ) -> bool {
+        let Some(area) = area else {
+            return self.geometry.intersects(region);
+        };
+        match rule {
+            BurnRule::Centroid => area.intersects(&zone.center),
+            BurnRule::Intersects => area.intersects(region),
+            BurnRule::MajorityArea => {
+                let inside = area_km2(&area.intersection(region));
+                inside > 0.0 && inside >= 0.5 * area_km2(region)
+            }
+        }
+    }
+}
+
+/// The bounding box of all the features.
+fn extent(features: &[Burn]) -> Option<Rect> {
+    features
+        .iter()
+        .filter_map(|feature| feature.geometry.bounding_rect())
+        .reduce(|a, b| {
+            Rect::new(
+                coord! { x: a.min().x.min(b.min().x), y: a.min().y.min(b.min().y) },
+                coord! { x: a.max().x.max(b.max().x), y: a.max().y.max(b.max().y) },
+            )
+        })
+}
+
+impl Coverage<f64> {
+    /// A coverage at `depth` of the features burnt in, with blocks at
+    /// `block_depth` covering their extent, see [`Coverage::burn`].
+    pub fn from_features(
+        name: &str,
+        dggrs: Arc<dyn DggrsPort>,
+        depth: u8,
+        block_depth: u8,
+        features: &[Burn],
+        rule: BurnRule,
+        conflict: Conflict,
+    ) -> Result<Self, String> {
+        let mut coverage = Coverage::new(name, dggrs, depth, block_depth)?;
+        if let Some(extent) = extent(features) {
+            coverage.add_blocks_in_bbox(Some(vec![
+                vec![extent.min().x, extent.min().y],
+                vec![extent.max().x, extent.max().y],
+            ]))?;
+        }
+        coverage.burn(features, rule, conflict)?;
+        Ok(coverage)
+    }
+
+    /// Burns the values of the features, e.g. land use classes or road
+    /// presence, into the cells selected by `rule`, resolving cells covered by
+    /// several features with `conflict`. Cells covered by no feature are left
+    /// unchanged. The candidate cells are the zones the DGGRS returns for the
+    /// extent of all the features, in a single query, and zones crossing the
+    /// antimeridian are split at it.
+    pub fn burn(
+        &mut self,
+        features: &[Burn],
+        rule: BurnRule,
+        conflict: Conflict,
+    ) -> Result<(), String> {
+        let areas: Vec<Option<MultiPolygon>> = features.iter().map(Burn::area).collect();
+        let options = match rule {
+            BurnRule::Centroid if areas.iter().all(Option::is_some) => {
+                ZoneQueryOptions::centroid_only()
+            }
+            _ => ZoneQueryOptions {
+                children: false,
+                neighbors: false,
+                ..ZoneQueryOptions::default()
+            },
+        };
+        let Some(extent) = extent(features) else {
+            return Ok(());
+        };
+        let bbox = vec![
+            vec![extent.min().x, extent.min().y],
+            vec![extent.max().x, extent.max().y],
+        ];
+        let zones = self
+            .dggrs
+            .zones_from_bbox(self.metadata.depth, &options, Some(bbox));
+        let rects: Vec<Option<Rect>> = features
+            .iter()
+            .map(|feature| feature.geometry.bounding_rect())
+            .collect();
+
+        // Per cell, the features burnt into it in the order given
+        let mut burnt: BTreeMap<ZoneID, Vec<&Burn>> = BTreeMap::new();
+        for zone in &zones {
+            if !self.contains(&zone.id) {
+                continue;
+            }
+            let region = split_antimeridian(&zone.region);
+            let bounds = region.bounding_rect();
+            for ((feature, area), rect) in features.iter().zip(&areas).zip(&rects) {
+                // Features far from the zone are skipped on their bounding box
+                let near = rect.is_some_and(|rect| match bounds {
+                    Some(bounds) => rect.intersects(&bounds),
+                    None => rect.intersects(&zone.center),
+                });
+                if near && feature.covers(area.as_ref(), zone, &region, rule) {
+                    burnt.entry(zone.id.clone()).or_default().push(feature);
+                }
+            }
+        }
+
+        for (id, features) in burnt {
+            let values = features.iter().map(|feature| feature.value);
+            let value = match conflict {
+                Conflict::Priority => features
+                    .iter()
+                    .rev()
+                    .max_by_key(|feature| feature.priority)
+                    .map(|feature| feature.value),
+                Conflict::Sum => Some(values.sum()),
+                Conflict::Mean => Some(values.sum::<f64>() / features.len() as f64),
+                Conflict::Min => values.reduce(f64::min),
+                Conflict::Max => values.reduce(f64::max),
+                Conflict::Count => Some(features.len() as f64),
+            };
+            if let Some(value) = value {
+                self.set(&id, value)?;
+            }
+        }
+        Ok(())
+    }
+}
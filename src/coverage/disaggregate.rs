@@ -0,0 +1,121 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::{Coverage, overlaps, zones_in_blocks};
+use std::collections::HashMap;
+
+/// How the value of a zone is distributed over the finer zones within it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Disaggregation {
+    /// Every finer zone takes the value of the zone, for intensive quantities
+    /// such as temperatures or densities.
+    Copy,
+    /// The value is split over the finer zones in proportion to their area, for
+    /// extensive quantities such as population counts. The total is preserved.
+    AreaSplit,
+}
+
+impl Coverage<f64> {
+    /// Distributes the values of the coverage over a finer coverage at `depth`,
+    /// with the same blocks. The finer zones of a cell are its sub-zones from
+    /// `zones_from_parent`, and those of its neighbours straddling it as happens
+    /// with aperture 3 and 7 hexagons. A straddling zone takes the area-weighted
+    /// mean of the copied values, or the sum of its shares of the split values.
+    ///
+    /// With `AreaSplit`, the optional `weights` at `depth` make the share of a
+    /// finer zone proportional to its area times its weight, for dasymetric
+    /// mapping. Cells whose finer zones have no positive weight are split by
+    /// area alone. The weights are ignored with `Copy`.
+    pub fn disaggregate(
+        &self,
+        depth: u8,
+        method: Disaggregation,
+        weights: Option<&Coverage<f64>>,
+    ) -> Result<Coverage<f64>, String> {
+        if depth <= self.metadata.depth {
+            return Err(format!(
+                "Depth {} is not finer than the coverage depth {}",
+                depth, self.metadata.depth
+            ));
+        }
+        if let Some(weights) = weights
+            && weights.resolution() != depth
+        {
+            return Err(format!(
+                "The weights are at depth {} instead of {}",
+                weights.resolution(),
+                depth
+            ));
+        }
+
+        let dggrs = self.dggrs.as_ref();
+        let blocks = self.zone_ids();
+        let mut target = Coverage::new(
+            &self.metadata.dggrs,
+            self.dggrs.clone(),
+            depth,
+            self.metadata.block_depth,
+        )?;
+        for block in &blocks {
+            target.add_block(block)?;
+        }
+        let parents = zones_in_blocks(dggrs, &blocks, self.metadata.depth, |id| {
+            self.get(id).is_some()
+        });
+        let overlaps = overlaps(dggrs, &parents, depth, |id| target.contains(id));
+
+        // Per finer zone, the accumulated value and area of the contributing cells
+        let mut values: HashMap<usize, (f64, f64)> = HashMap::new();
+        for (parent, pairs) in parents.iter().zip(&overlaps.pairs) {
+            let Some(&value) = self.get(&parent.id) else {
+                continue;
+            };
+            match method {
+                Disaggregation::Copy => {
+                    for &(i, area) in pairs {
+                        let entry = values.entry(i).or_default();
+                        entry.0 += value * area;
+                        entry.1 += area;
+                    }
+                }
+                Disaggregation::AreaSplit => {
+                    let weight = |i: usize| {
+                        weights
+                            .and_then(|w| w.get(&overlaps.cells[i].id))
+                            .copied()
+                            .unwrap_or(0.0)
+                            .max(0.0)
+                    };
+                    let mut shares: Vec<(usize, f64)> = pairs
+                        .iter()
+                        .map(|&(i, area)| (i, area * weight(i)))
+                        .collect();
+                    if weights.is_none() || shares.iter().all(|&(_, share)| share == 0.0) {
+                        shares = pairs.clone();
+                    }
+                    let total: f64 = shares.iter().map(|&(_, share)| share).sum();
+                    for (i, share) in shares {
+                        if total > 0.0 {
+                            values.entry(i).or_default().0 += value * share / total;
+                        }
+                    }
+                }
+            }
+        }
+
+        for (i, (value, area)) in values {
+            let value = match method {
+                Disaggregation::Copy => value / area,
+                Disaggregation::AreaSplit => value,
+            };
+            target.set(&overlaps.cells[i].id, value)?;
+        }
+        Ok(target)
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::Coverage;
+use crate::models::common::{ZoneID, ZoneQueryOptions};
+use std::collections::{HashMap, HashSet};
+
+/// The statistic computed over the neighbourhood of each cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focal {
+    Mean,
+    Sum,
+    Min,
+    Max,
+    StdDev,
+}
+
+/// Weight of a cell in the neighbourhood according to its ring, the number of
+/// steps from the central cell, 0 for the central cell itself. Rings stand in
+/// for the distance, all cells of a ring weigh the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decay {
+    /// All cells weigh 1.
+    Uniform,
+    /// Cells weigh `1 / (ring + 1)^power`.
+    InverseRing(f64),
+    /// Cells weigh `exp(-ring² / 2σ²)`, with σ given in rings.
+    GaussianRing(f64),
+}
+
+impl Decay {
+    pub fn weight(&self, ring: u8) -> f64 {
+        let ring = ring as f64;
+        match self {
+            Decay::Uniform => 1.0,
+            Decay::InverseRing(power) => (ring + 1.0).powf(-power),
+            Decay::GaussianRing(sigma) => (-(ring * ring) / (2.0 * sigma * sigma)).exp(),
+        }
+    }
+}
+
+/// A cell with a value in the neighbourhood of another.
+#[derive(Debug, Clone, Copy)]
+pub struct Neighbour<'a, T> {
+    pub id: &'a ZoneID,
+    pub value: &'a T,
+    /// Number of steps from the central cell, 0 for the central cell itself.
+    pub ring: u8,
+}
+
+impl<T> Coverage<T> {
+    /// Computes a new value for every cell holding one from the cells with a
+    /// value within `radius` rings of it, the k-ring following `Zone::neighbors`.
+    /// The neighbourhood is passed to `f` in ring order, the central cell first.
+    ///
+    /// The rings follow the neighbours of all zones, including those outside the
+    /// coverage, so a gap in the coverage does not cut the neighbourhood short.
+    /// Neighbourhoods are truncated at the edge of the coverage, cells without
+    /// value are left out, and pentagons have five neighbours instead of six, so
+    /// `f` gets fewer cells there and should normalise by what it gets.
+    pub fn focal_with<U, F>(&self, radius: u8, f: F) -> Result<Coverage<U>, String>
+    where
+        F: Fn(&[Neighbour<'_, T>]) -> Option<U>,
+    {
+        let topology = self.topology(radius);
+        let mut target = self.empty_like();
+
+        for (id, _) in self.iter() {
+            let mut window = vec![];
+            let mut visited: HashSet<&ZoneID> = HashSet::from([id]);
+            let mut ring: Vec<&ZoneID> = vec![id];
+            for k in 0..=radius {
+                for &cell in &ring {
+                    if let Some(value) = self.get(cell) {
+                        window.push(Neighbour {
+                            id: cell,
+                            value,
+                            ring: k,
+                        });
+                    }
+                }
+                if k == radius {
+                    break;
+                }
+                ring = ring
+                    .iter()
+                    .filter_map(|cell| topology.get(*cell))
+                    .flatten()
+                    .filter(|neighbor| visited.insert(*neighbor))
+                    .collect();
+            }
+
+            if let Some(value) = f(&window) {
+                target.set(id, value)?;
+            }
+        }
+        Ok(target)
+    }
+
+    /// The neighbours of every cell, as given by the DGGRS, and of the zones
+    /// outside the coverage less than `radius` rings away. Those are taken from
+    /// the blocks around the coverage, with one query per ring.
+    fn topology(&self, radius: u8) -> HashMap<ZoneID, Vec<ZoneID>> {
+        let options = ZoneQueryOptions {
+            geometry: false,
+            children: false,
+            ..ZoneQueryOptions::default()
+        };
+        let mut topology: HashMap<ZoneID, Vec<ZoneID>> = self
+            .cell_zones(&options)
+            .into_iter()
+            .map(|zone| (zone.id, zone.neighbors))
+            .collect();
+
+        let mut blocks: HashSet<ZoneID> =
+            self.blocks.iter().map(|block| block.id.clone()).collect();
+        for _ in 1..radius {
+            let missing: HashSet<&ZoneID> = topology
+                .values()
+                .flatten()
+                .filter(|id| !topology.contains_key(*id))
+                .collect();
+            let around: Vec<ZoneID> = missing
+                .into_iter()
+                .filter_map(|id| self.dggrs.zone_ancestor(id, self.metadata.block_depth))
+                .filter(|block| !blocks.contains(block))
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            if around.is_empty() {
+                break;
+            }
+            for zone in self
+                .dggrs
+                .zones_from_parents(self.metadata.depth, &around, &options)
+            {
+                topology.entry(zone.id).or_insert(zone.neighbors);
+            }
+            blocks.extend(around);
+        }
+        topology
+    }
+}
+
+impl Coverage<f64> {
+    /// Computes `statistic` over the cells within `radius` rings of each cell,
+    /// weighted by `decay`. Minimum and maximum ignore the weights.
+    pub fn focal(
+        &self,
+        radius: u8,
+        statistic: Focal,
+        decay: Decay,
+    ) -> Result<Coverage<f64>, String> {
+        self.focal_with(radius, |window| {
+            let weighted = window.iter().map(|n| (*n.value, decay.weight(n.ring)));
+            match statistic {
+                Focal::Sum => Some(weighted.map(|(v, w)| v * w).sum()),
+                Focal::Mean => weighted_mean(weighted),
+                Focal::Min => window.iter().map(|n| *n.value).reduce(f64::min),
+                Focal::Max => window.iter().map(|n| *n.value).reduce(f64::max),
+                Focal::StdDev => {
+                    let mean = weighted_mean(weighted.clone())?;
+                    weighted_mean(weighted.map(|(v, w)| ((v - mean).powi(2), w))).map(f64::sqrt)
+                }
+            }
+        })
+    }
+
+    /// Convolves the coverage with a kernel giving the weight of each ring, the
+    /// central cell first, so the radius is the kernel length minus one. The
+    /// weighted sum is normalised by the weights of the cells present, which keeps
+    /// the edges of the coverage and pentagons unbiased.
+    pub fn focal_kernel(&self, kernel: &[f64]) -> Result<Coverage<f64>, String> {
+        if kernel.is_empty() || kernel.len() > u8::MAX as usize {
+            return Err(format!("Invalid kernel length {}", kernel.len()));
+        }
+        self.focal_with((kernel.len() - 1) as u8, |window| {
+            weighted_mean(window.iter().map(|n| (*n.value, kernel[n.ring as usize])))
+        })
+    }
+}
+
+fn weighted_mean<I: Iterator<Item = (f64, f64)>>(values: I) -> Option<f64> {
+    let (sum, total) = values.fold((0.0, 0.0), |(sum, total), (v, w)| (sum + v * w, total + w));
+    if total != 0.0 {
+        Some(sum / total)
+    } else {
+        None
+    }
+}
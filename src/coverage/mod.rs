@@ -0,0 +1,398 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Aggregation of coverage values into coarser zones.
+pub mod aggregate;
+/// Cell by cell operations between coverages.
+pub mod algebra;
+/// Binning of point observations into coverages.
+pub mod binning;
+/// Burning of vector features into coverages.
+pub mod burn;
+/// Distribution of coverage values over finer zones.
+pub mod disaggregate;
+/// Neighbourhood operations on coverages.
+pub mod focal;
+/// Exchange of coverages with regular longitude and latitude rasters.
+pub mod raster;
+/// Statistics of coverages within polygons.
+pub mod zonal;
+
+use crate::encoding::antimeridian::split_antimeridian;
+use crate::models::common::{Zone, ZoneID, ZoneQueryOptions};
+use crate::ports::dggrs::DggrsPort;
+use geo::{BooleanOps, ChamberlainDuquetteArea, MultiPolygon};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+/// Identifies what a coverage holds: the DGGRS, the type of its values, the
+/// depth of its cells and the depth of the blocks organising them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageMetadata {
+    /// Name of the DGGRS, e.g. `IGEO7`.
+    pub dggrs: String,
+    /// Rust type name of the values.
+    pub value_type: String,
+    /// Depth of the cells holding the values.
+    pub depth: u8,
+    /// Depth of the blocks, coarser than the cells.
+    pub block_depth: u8,
+}
+
+/// The cells of one block, in the order returned by `zones_from_parent`, with
+/// their values.
+#[derive(Debug, Clone)]
+struct Block<T> {
+    id: ZoneID,
+    cells: Vec<ZoneID>,
+    values: Vec<Option<T>>,
+}
+
+/// A coverage maps the zones of a DGGRS at a fixed depth, its cells, to values.
+/// The cells are organised into blocks, zones at a coarser depth whose
+/// sub-zones are the cells, which are the unit of processing and encoding, as
+/// the zones of the OGC API for DGGS. A cell without value holds `None`.
+///
+/// Blocks are added explicitly, the cells of a block are given by the DGGRS
+/// through `zones_from_parent`. A cell returned for several blocks, as happens
+/// with aperture 3 and 7 hexagons, belongs to the block it was first added with.
+pub struct Coverage<T> {
+    dggrs: Arc<dyn DggrsPort>,
+    metadata: CoverageMetadata,
+    blocks: Vec<Block<T>>,
+    block_index: HashMap<ZoneID, usize>,
+    cell_index: HashMap<ZoneID, (usize, usize)>,
+}
+
+impl<T> Coverage<T> {
+    /// An empty coverage of the DGGRS `name`, served by `dggrs`, with cells at
+    /// `depth` organised into blocks at `block_depth`.
+    pub fn new(
+        name: &str,
+        dggrs: Arc<dyn DggrsPort>,
+        depth: u8,
+        block_depth: u8,
+    ) -> Result<Self, String> {
+        if depth > dggrs.max_depth() {
+            return Err(format!(
+                "Depth {} exceeds the maximum depth {} of {}",
+                depth,
+                dggrs.max_depth(),
+                name
+            ));
+        }
+        if block_depth >= depth {
+            return Err(format!(
+                "Block depth {} must be coarser than the cell depth {}",
+                block_depth, depth
+            ));
+        }
+        Ok(Self {
+            dggrs,
+            metadata: CoverageMetadata {
+                dggrs: name.to_string(),
+                value_type: std::any::type_name::<T>().to_string(),
+                depth,
+                block_depth,
+            },
+            blocks: vec![],
+            block_index: HashMap::new(),
+            cell_index: HashMap::new(),
+        })
+    }
+
+    /// Adds the block `id` with all its cells empty. Adding an existing block does nothing.
+    pub fn add_block(&mut self, id: &ZoneID) -> Result<(), String> {
+        if id.depth() != Some(self.metadata.block_depth) {
+            return Err(format!(
+                "Block {} is not at the block depth {}",
+                id, self.metadata.block_depth
+            ));
+        }
+        if self.block_index.contains_key(id) {
+            return Ok(());
+        }
+
+        let block = self.blocks.len();
+        let mut cells = vec![];
+        for zone in self.dggrs.zones_from_parent(
+            self.metadata.depth,
+            id.id.clone(),
+            &ZoneQueryOptions::centroid_only(),
+        ) {
+            if !self.cell_index.contains_key(&zone.id) {
+                self.cell_index
+                    .insert(zone.id.clone(), (block, cells.len()));
+                cells.push(zone.id);
+            }
+        }
+
+        let values = std::iter::repeat_with(|| None).take(cells.len()).collect();
+        self.blocks.push(Block {
+            id: id.clone(),
+            cells,
+            values,
+        });
+        self.block_index.insert(id.clone(), block);
+        Ok(())
+    }
+
+    /// Adds every block within the bounding box, or of the whole globe without one.
+    pub fn add_blocks_in_bbox(&mut self, bbox: Option<Vec<Vec<f64>>>) -> Result<(), String> {
+        let blocks = self.dggrs.zones_from_bbox(
+            self.metadata.block_depth,
+            &ZoneQueryOptions::centroid_only(),
+            bbox,
+        );
+        for block in blocks {
+            self.add_block(&block.id)?;
+        }
+        Ok(())
+    }
+
+    pub fn metadata(&self) -> &CoverageMetadata {
+        &self.metadata
+    }
+
+    /// The port serving the DGGRS of the coverage.
+    pub fn dggrs_port(&self) -> &Arc<dyn DggrsPort> {
+        &self.dggrs
+    }
+
+    /// Identifies the DGGRS on which the coverage is based.
+    pub fn dggrs(&self) -> &str {
+        &self.metadata.dggrs
+    }
+
+    /// Identifies the type of the values stored by the coverage.
+    pub fn value_type(&self) -> &str {
+        &self.metadata.value_type
+    }
+
+    /// The depth of the cells, which determines their size.
+    pub fn resolution(&self) -> u8 {
+        self.metadata.depth
+    }
+
+    /// The depth of the blocks organising the cells.
+    pub fn zone_resolution(&self) -> u8 {
+        self.metadata.block_depth
+    }
+
+    /// The ids of the blocks, in the order they were added.
+    pub fn zone_ids(&self) -> Vec<ZoneID> {
+        self.blocks.iter().map(|block| block.id.clone()).collect()
+    }
+
+    /// Whether `id` is a cell of the coverage, with or without value.
+    pub fn contains(&self, id: &ZoneID) -> bool {
+        self.cell_index.contains_key(id)
+    }
+
+    /// Number of cells of the coverage, with or without value.
+    pub fn len(&self) -> usize {
+        self.cell_index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cell_index.is_empty()
+    }
+
+    /// The value of a cell, `None` when it has no value or is not part of the coverage.
+    pub fn get(&self, id: &ZoneID) -> Option<&T> {
+        let &(block, cell) = self.cell_index.get(id)?;
+        self.blocks[block].values[cell].as_ref()
+    }
+
+    /// Sets the value of a cell, returning the previous one. Fails when the
+    /// cell is not part of any block of the coverage.
+    pub fn set(&mut self, id: &ZoneID, value: T) -> Result<Option<T>, String> {
+        let &(block, cell) = self
+            .cell_index
+            .get(id)
+            .ok_or_else(|| format!("Zone {} is not a cell of the coverage", id))?;
+        Ok(self.blocks[block].values[cell].replace(value))
+    }
+
+    /// Removes the value of a cell, returning it.
+    pub fn unset(&mut self, id: &ZoneID) -> Option<T> {
+        let &(block, cell) = self.cell_index.get(id)?;
+        self.blocks[block].values[cell].take()
+    }
+
+    /// The cells of a block, in the order of its values.
+    pub fn zone_cells(&self, id: &ZoneID) -> Option<&[ZoneID]> {
+        let &block = self.block_index.get(id)?;
+        Some(&self.blocks[block].cells)
+    }
+
+    /// The values of all cells of a block, in the order of `zone_cells`.
+    pub fn get_zone(&self, id: &ZoneID) -> Option<&[Option<T>]> {
+        let &block = self.block_index.get(id)?;
+        Some(&self.blocks[block].values)
+    }
+
+    /// Every cell with its value, block by block.
+    pub fn cells(&self) -> impl Iterator<Item = (&ZoneID, Option<&T>)> {
+        self.blocks.iter().flat_map(|block| {
+            block
+                .cells
+                .iter()
+                .zip(block.values.iter().map(Option::as_ref))
+        })
+    }
+
+    /// The cells holding a value, with the value.
+    pub fn iter(&self) -> impl Iterator<Item = (&ZoneID, &T)> {
+        self.cells()
+            .filter_map(|(id, value)| value.map(|value| (id, value)))
+    }
+
+    /// The cells of the coverage as zones from the DGGRS, computed as per `options`,
+    /// with one query for all the blocks.
+    fn cell_zones(&self, options: &ZoneQueryOptions) -> Vec<Zone> {
+        let blocks: Vec<ZoneID> = self.blocks.iter().map(|block| block.id.clone()).collect();
+        self.dggrs
+            .zones_from_parents(self.metadata.depth, &blocks, options)
+            .into_iter()
+            .filter(|zone| self.cell_index.contains_key(&zone.id))
+            .collect()
+    }
+
+    /// An empty coverage with the same DGGRS, depths and blocks as this one.
+    pub fn empty_like<U>(&self) -> Coverage<U> {
+        Coverage {
+            dggrs: self.dggrs.clone(),
+            metadata: CoverageMetadata {
+                value_type: std::any::type_name::<U>().to_string(),
+                ..self.metadata.clone()
+            },
+            blocks: self
+                .blocks
+                .iter()
+                .map(|block| Block {
+                    id: block.id.clone(),
+                    cells: block.cells.clone(),
+                    values: std::iter::repeat_with(|| None)
+                        .take(block.cells.len())
+                        .collect(),
+                })
+                .collect(),
+            block_index: self.block_index.clone(),
+            cell_index: self.cell_index.clone(),
+        }
+    }
+}
+
+impl<T: Clone> Clone for Coverage<T> {
+    fn clone(&self) -> Self {
+        Self {
+            dggrs: self.dggrs.clone(),
+            metadata: self.metadata.clone(),
+            blocks: self.blocks.clone(),
+            block_index: self.block_index.clone(),
+            cell_index: self.cell_index.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Coverage<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Coverage")
+            .field("metadata", &self.metadata)
+            .field("blocks", &self.blocks.len())
+            .field("cells", &self.cell_index.len())
+            .finish()
+    }
+}
+
+/// Area on the sphere of a geometry in longitude and latitude, in km².
+fn area_km2<G: ChamberlainDuquetteArea<f64>>(geometry: &G) -> f64 {
+    geometry.chamberlain_duquette_unsigned_area() / 1e6
+}
+
+/// The zones at `depth` within `blocks` accepted by `keep`, with their region and neighbours.
+fn zones_in_blocks<F>(dggrs: &dyn DggrsPort, blocks: &[ZoneID], depth: u8, keep: F) -> Vec<Zone>
+where
+    F: Fn(&ZoneID) -> bool,
+{
+    let options = ZoneQueryOptions {
+        children: false,
+        ..ZoneQueryOptions::default()
+    };
+    dggrs
+        .zones_from_parents(depth, blocks, &options)
+        .into_iter()
+        .filter(|zone| keep(&zone.id))
+        .collect()
+}
+
+/// The zones at a finer depth overlapping a set of coarser zones.
+struct Overlaps {
+    cells: Vec<Zone>,
+    /// For each coarser zone, the index of every overlapping cell with the area of
+    /// the overlap in km².
+    pairs: Vec<Vec<(usize, f64)>>,
+}
+
+/// Pairs every zone of `coarse` with the zones at `depth` accepted by `keep` it
+/// overlaps. The sub-zones of all the coarse zones are queried at once and filed
+/// under the coarse zone they descend from. The candidates of a coarse zone are
+/// those of the zone and of its neighbours, which covers the cells straddling
+/// coarse zones with aperture 3 and 7 hexagons.
+fn overlaps<F>(dggrs: &dyn DggrsPort, coarse: &[Zone], depth: u8, keep: F) -> Overlaps
+where
+    F: Fn(&ZoneID) -> bool,
+{
+    let options = ZoneQueryOptions {
+        children: false,
+        neighbors: false,
+        ..ZoneQueryOptions::default()
+    };
+    let ids: Vec<ZoneID> = coarse.iter().map(|zone| zone.id.clone()).collect();
+    let coarse_depth = ids.first().and_then(ZoneID::depth);
+    let mut cells: Vec<Zone> = vec![];
+    let mut sub_zones: HashMap<ZoneID, Vec<usize>> = HashMap::new();
+    for cell in dggrs.zones_from_parents(depth, &ids, &options) {
+        if !keep(&cell.id) {
+            continue;
+        }
+        if let Some(parent) = coarse_depth.and_then(|d| dggrs.zone_ancestor(&cell.id, d)) {
+            sub_zones.entry(parent).or_default().push(cells.len());
+            cells.push(cell);
+        }
+    }
+
+    // Regions crossing the antimeridian are split, so their parts on either side overlap
+    let regions: Vec<MultiPolygon> = cells
+        .iter()
+        .map(|cell| split_antimeridian(&cell.region))
+        .collect();
+    let pairs = coarse
+        .iter()
+        .map(|zone| {
+            let region = split_antimeridian(&zone.region);
+            let zone_area = area_km2(&region);
+            let mut seen = HashSet::new();
+            std::iter::once(&zone.id)
+                .chain(&zone.neighbors)
+                .filter_map(|id| sub_zones.get(id))
+                .flatten()
+                .filter(|&&i| seen.insert(i))
+                .filter_map(|&i| {
+                    let area = area_km2(&region.intersection(&regions[i]));
+                    // Cells merely touching the zone have no area inside it
+                    (area > zone_area * 1e-9).then_some((i, area))
+                })
+                .collect()
+        })
+        .collect();
+    Overlaps { cells, pairs }
+}
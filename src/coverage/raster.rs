@@ -0,0 +1,385 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::{Coverage, area_km2};
+use crate::encoding::antimeridian::split_antimeridian;
+use crate::models::common::{Zone, ZoneQueryOptions};
+use crate::ports::dggrs::DggrsPort;
+use geo::{BooleanOps, BoundingRect, Coord, LineString, Point, Polygon, Rect, Translate};
+use std::sync::Arc;
+
+/// Radius of the WGS84 ellipsoid used by the Web Mercator projection, in metres.
+const WEB_MERCATOR_RADIUS: f64 = 6378137.0;
+
+/// Coordinate reference system of a raster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Projection {
+    /// Longitude and latitude in degrees, EPSG:4326.
+    #[default]
+    Geographic,
+    /// Web Mercator in metres, EPSG:3857.
+    WebMercator,
+}
+
+impl Projection {
+    /// The EPSG code of the coordinate reference system.
+    pub fn epsg(&self) -> u16 {
+        match self {
+            Projection::Geographic => 4326,
+            Projection::WebMercator => 3857,
+        }
+    }
+
+    /// Projected coordinates of a longitude and latitude.
+    pub fn project(&self, coord: Coord) -> Coord {
+        match self {
+            Projection::Geographic => coord,
+            Projection::WebMercator => {
+                // Beyond ±85.06° the projection diverges
+                let lat = coord.y.clamp(-85.0511287798, 85.0511287798).to_radians();
+                Coord {
+                    x: WEB_MERCATOR_RADIUS * coord.x.to_radians(),
+                    y: WEB_MERCATOR_RADIUS * (std::f64::consts::FRAC_PI_4 + lat / 2.0).tan().ln(),
+                }
+            }
+        }
+    }
+
+    /// Longitude and latitude of projected coordinates.
+    pub fn unproject(&self, coord: Coord) -> Coord {
+        match self {
+            Projection::Geographic => coord,
+            Projection::WebMercator => Coord {
+                x: (coord.x / WEB_MERCATOR_RADIUS).to_degrees(),
+                y: (2.0 * (coord.y / WEB_MERCATOR_RADIUS).exp().atan()
+                    - std::f64::consts::FRAC_PI_2)
+                    .to_degrees(),
+            },
+        }
+    }
+}
+
+/// A regular grid of values held in memory in row major order, e.g. the output
+/// of a climate model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raster {
+    pub width: usize,
+    pub height: usize,
+    /// Affine transform from pixel to projected coordinates, in the order used
+    /// by GDAL: the x of a pixel corner is `gt[0] + col * gt[1] + row * gt[2]`
+    /// and its y `gt[3] + col * gt[4] + row * gt[5]`.
+    pub geotransform: [f64; 6],
+    pub projection: Projection,
+    pub data: Vec<f64>,
+    /// Value marking pixels without data. NaN pixels are always without data.
+    pub nodata: Option<f64>,
+}
+
+/// How the raster is sampled for each zone of the coverage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resampling {
+    /// The pixel containing the zone centroid.
+    Nearest,
+    /// Bilinear interpolation between the four pixel centres around the zone centroid.
+    Bilinear,
+    /// Mean of the pixels overlapping the zone polygon, weighted by the area of the overlap.
+    AreaWeighted,
+}
+
+impl Raster {
+    pub fn new(
+        width: usize,
+        height: usize,
+        geotransform: [f64; 6],
+        data: Vec<f64>,
+    ) -> Result<Self, String> {
+        if data.len() != width * height {
+            return Err(format!(
+                "Expected {} values for a {} by {} raster, got {}",
+                width * height,
+                width,
+                height,
+                data.len()
+            ));
+        }
+        if geotransform[1] * geotransform[5] - geotransform[2] * geotransform[4] == 0.0 {
+            return Err("The geotransform is not invertible".to_string());
+        }
+        Ok(Self {
+            width,
+            height,
+            geotransform,
+            projection: Projection::Geographic,
+            data,
+            nodata: None,
+        })
+    }
+
+    pub fn with_nodata(mut self, nodata: f64) -> Self {
+        self.nodata = Some(nodata);
+        self
+    }
+
+    pub fn with_projection(mut self, projection: Projection) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    /// The value of a pixel, `None` outside the raster or without data.
+    pub fn get(&self, col: i64, row: i64) -> Option<f64> {
+        if col < 0 || row < 0 || col as usize >= self.width || row as usize >= self.height {
+            return None;
+        }
+        let value = self.data[row as usize * self.width + col as usize];
+        if value.is_nan() || self.nodata == Some(value) {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Longitude and latitude of a position in pixel space.
+    pub fn to_geo(&self, col: f64, row: f64) -> Coord {
+        let gt = &self.geotransform;
+        self.projection.unproject(Coord {
+            x: gt[0] + col * gt[1] + row * gt[2],
+            y: gt[3] + col * gt[4] + row * gt[5],
+        })
+    }
+
+    /// Position in pixel space of a longitude and latitude, trying the longitude
+    /// shifted by ±360° as well for rasters spanning 0° to 360°.
+    pub fn to_pixel(&self, coord: Coord) -> (f64, f64) {
+        [coord.x, coord.x + 360.0, coord.x - 360.0]
+            .into_iter()
+            .map(|x| self.invert(Coord { x, y: coord.y }))
+            .find(|&pixel| self.contains_pixel(pixel))
+            .unwrap_or_else(|| self.invert(coord))
+    }
+
+    /// Position in pixel space of a longitude and latitude, without wrapping.
+    fn invert(&self, coord: Coord) -> (f64, f64) {
+        let gt = &self.geotransform;
+        let det = gt[1] * gt[5] - gt[2] * gt[4];
+        let projected = self.projection.project(coord);
+        let (dx, dy) = (projected.x - gt[0], projected.y - gt[3]);
+        (
+            (gt[5] * dx - gt[2] * dy) / det,
+            (gt[1] * dy - gt[4] * dx) / det,
+        )
+    }
+
+    fn contains_pixel(&self, (col, row): (f64, f64)) -> bool {
+        col >= 0.0 && row >= 0.0 && col < self.width as f64 && row < self.height as f64
+    }
+
+    /// The outline of a pixel in longitude and latitude.
+    pub fn pixel_polygon(&self, col: i64, row: i64) -> Polygon {
+        let (c, r) = (col as f64, row as f64);
+        Polygon::new(
+            LineString::from(vec![
+                self.to_geo(c, r),
+                self.to_geo(c + 1.0, r),
+                self.to_geo(c + 1.0, r + 1.0),
+                self.to_geo(c, r + 1.0),
+                self.to_geo(c, r),
+            ]),
+            vec![],
+        )
+    }
+
+    /// The bounding box of the raster as `[[min lon, min lat], [max lon, max lat]]`,
+    /// limited to the globe.
+    pub fn bbox(&self) -> Vec<Vec<f64>> {
+        let corners = [
+            self.to_geo(0.0, 0.0),
+            self.to_geo(self.width as f64, 0.0),
+            self.to_geo(0.0, self.height as f64),
+            self.to_geo(self.width as f64, self.height as f64),
+        ];
+        let fold = |f: fn(f64, f64) -> f64, init: f64, axis: fn(&Coord) -> f64| {
+            corners.iter().map(axis).fold(init, f)
+        };
+        let (mut min_x, mut max_x) = (
+            fold(f64::min, f64::INFINITY, |c| c.x),
+            fold(f64::max, f64::NEG_INFINITY, |c| c.x),
+        );
+        if max_x > 180.0 || min_x < -180.0 {
+            (min_x, max_x) = (-180.0, 180.0);
+        }
+        vec![
+            vec![min_x, fold(f64::min, f64::INFINITY, |c| c.y).max(-90.0)],
+            vec![max_x, fold(f64::max, f64::NEG_INFINITY, |c| c.y).min(90.0)],
+        ]
+    }
+
+    fn sample_nearest(&self, zone: &Zone) -> Option<f64> {
+        let (col, row) = self.to_pixel(zone.center.0);
+        self.get(col.floor() as i64, row.floor() as i64)
+    }
+
+    fn sample_bilinear(&self, zone: &Zone) -> Option<f64> {
+        let (col, row) = self.to_pixel(zone.center.0);
+        // Relative to the pixel centres
+        let (col, row) = (col - 0.5, row - 0.5);
+        let (c0, r0) = (col.floor(), row.floor());
+        let (tc, tr) = (col - c0, row - r0);
+
+        let mut sum = 0.0;
+        let mut total = 0.0;
+        for (dc, dr, weight) in [
+            (0, 0, (1.0 - tc) * (1.0 - tr)),
+            (1, 0, tc * (1.0 - tr)),
+            (0, 1, (1.0 - tc) * tr),
+            (1, 1, tc * tr),
+        ] {
+            // Missing pixels at the edges or without data are left out
+            if let Some(value) = self.get(c0 as i64 + dc, r0 as i64 + dr)
+                && weight > 0.0
+            {
+                sum += value * weight;
+                total += weight;
+            }
+        }
+        (total > 0.0).then(|| sum / total)
+    }
+
+    fn sample_area_weighted(&self, zone: &Zone) -> Option<f64> {
+        let mut sum = 0.0;
+        let mut total = 0.0;
+        // The zone is split at the antimeridian and each part is moved by ±360° as
+        // well, into the longitude range of rasters spanning 0° to 360°
+        for part in split_antimeridian(&zone.region) {
+            for shift in [0.0, 360.0, -360.0] {
+                let part = part.translate(shift, 0.0);
+                let Some(rect) = part.bounding_rect() else {
+                    continue;
+                };
+                let corners = [
+                    rect.min(),
+                    rect.max(),
+                    Coord {
+                        x: rect.min().x,
+                        y: rect.max().y,
+                    },
+                    Coord {
+                        x: rect.max().x,
+                        y: rect.min().y,
+                    },
+                ]
+                .map(|corner| self.invert(corner));
+                let col_range = corners.iter().map(|c| c.0.floor() as i64);
+                let row_range = corners.iter().map(|c| c.1.floor() as i64);
+                let (c0, c1) = (col_range.clone().min()?.max(0), col_range.max()?);
+                let (r0, r1) = (row_range.clone().min()?.max(0), row_range.max()?);
+
+                for row in r0..=r1.min(self.height as i64 - 1) {
+                    for col in c0..=c1.min(self.width as i64 - 1) {
+                        let Some(value) = self.get(col, row) else {
+                            continue;
+                        };
+                        let area = area_km2(&part.intersection(&self.pixel_polygon(col, row)));
+                        sum += value * area;
+                        total += area;
+                    }
+                }
+            }
+        }
+        (total > 0.0).then(|| sum / total)
+    }
+}
+
+impl Coverage<f64> {
+    /// A coverage at `depth` of the raster, with blocks at `block_depth` covering
+    /// its extent, see [`Coverage::sample_raster`].
+    pub fn from_raster(
+        name: &str,
+        dggrs: Arc<dyn DggrsPort>,
+        depth: u8,
+        block_depth: u8,
+        raster: &Raster,
+        method: Resampling,
+    ) -> Result<Self, String> {
+        let mut coverage = Coverage::new(name, dggrs, depth, block_depth)?;
+        coverage.add_blocks_in_bbox(Some(raster.bbox()))?;
+        coverage.sample_raster(raster, method)?;
+        Ok(coverage)
+    }
+
+    /// Sets the value of every cell covered by the raster, sampled with `method`.
+    /// Cells outside the raster or over pixels without data are left unchanged.
+    /// The area-weighted method fetches the zone polygons from the DGGRS, the
+    /// others only the centroids.
+    pub fn sample_raster(&mut self, raster: &Raster, method: Resampling) -> Result<(), String> {
+        let options = match method {
+            Resampling::AreaWeighted => ZoneQueryOptions {
+                children: false,
+                neighbors: false,
+                ..ZoneQueryOptions::default()
+            },
+            _ => ZoneQueryOptions::centroid_only(),
+        };
+        for zone in self.cell_zones(&options) {
+            let value = match method {
+                Resampling::Nearest => raster.sample_nearest(&zone),
+                Resampling::Bilinear => raster.sample_bilinear(&zone),
+                Resampling::AreaWeighted => raster.sample_area_weighted(&zone),
+            };
+            if let Some(value) = value {
+                self.set(&zone.id, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Coverage<f64> {
+    /// Renders the coverage onto a regular grid of `width` by `height` pixels
+    /// spanning `extent`, given in the coordinates of `projection`. Each pixel
+    /// takes the value of the zone containing its centre, found with the point
+    /// lookup of the DGGRS, or `nodata` when the zone has no value. Fails when
+    /// the lookup fails.
+    pub fn to_raster(
+        &self,
+        width: usize,
+        height: usize,
+        extent: Rect,
+        projection: Projection,
+        nodata: f64,
+    ) -> Result<Raster, String> {
+        if width == 0 || height == 0 {
+            return Err("The raster must have at least one pixel".to_string());
+        }
+        let geotransform = [
+            extent.min().x,
+            extent.width() / width as f64,
+            0.0,
+            extent.max().y,
+            0.0,
+            -extent.height() / height as f64,
+        ];
+        let mut raster = Raster::new(width, height, geotransform, vec![nodata; width * height])?
+            .with_projection(projection)
+            .with_nodata(nodata);
+
+        let centres: Vec<Point> = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (col, row)))
+            .map(|(col, row)| raster.to_geo(col as f64 + 0.5, row as f64 + 0.5).into())
+            .collect();
+        let ids = self
+            .dggrs
+            .zone_ids_from_points(self.metadata.depth, &centres)?;
+        for (pixel, id) in raster.data.iter_mut().zip(ids) {
+            if let Some(&value) = id.and_then(|id| self.get(&id)) {
+                *pixel = value;
+            }
+        }
+        Ok(raster)
+    }
+}
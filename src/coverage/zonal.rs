@@ -0,0 +1,117 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::{Coverage, area_km2};
+use crate::encoding::antimeridian::split_antimeridian;
+use crate::models::common::ZoneQueryOptions;
+use geo::{BooleanOps, BoundingRect, Intersects, MultiPolygon};
+
+/// Decides which cells of a coverage fall inside a polygon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Membership {
+    /// Cells whose centroid lies inside the polygon.
+    Centroid,
+    /// Cells with at least this fraction of their area inside the polygon, any
+    /// overlap with 0. Cells crossing the antimeridian are split at it first.
+    AreaFraction(f64),
+}
+
+/// Statistics of the values of the cells inside a polygon.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZonalStatistics {
+    pub count: usize,
+    pub sum: f64,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    /// The values in ascending order.
+    values: Vec<f64>,
+}
+
+impl ZonalStatistics {
+    fn new(mut values: Vec<f64>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        let sum: f64 = values.iter().sum();
+        Some(Self {
+            count: values.len(),
+            sum,
+            mean: sum / values.len() as f64,
+            min: values[0],
+            max: values[values.len() - 1],
+            values,
+        })
+    }
+
+    /// The `p`th percentile, 0 to 100, interpolating linearly between the
+    /// closest ranks.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let rank = p / 100.0 * (self.values.len() - 1) as f64;
+        let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+        let t = rank - lower as f64;
+        Some(self.values[lower] * (1.0 - t) + self.values[upper] * t)
+    }
+
+    pub fn median(&self) -> f64 {
+        self.percentile(50.0).unwrap_or(self.mean)
+    }
+}
+
+impl Coverage<f64> {
+    /// Statistics of the cells with a value inside each polygon, e.g. of
+    /// administrative units, `None` for polygons without any. The candidate cells
+    /// are the zones the DGGRS returns for the bounding box of the polygon.
+    pub fn zonal_statistics(
+        &self,
+        polygons: &[MultiPolygon],
+        membership: Membership,
+    ) -> Vec<Option<ZonalStatistics>> {
+        let options = match membership {
+            Membership::Centroid => ZoneQueryOptions::centroid_only(),
+            Membership::AreaFraction(_) => ZoneQueryOptions {
+                children: false,
+                neighbors: false,
+                ..ZoneQueryOptions::default()
+            },
+        };
+
+        polygons
+            .iter()
+            .map(|polygon| {
+                let rect = polygon.bounding_rect()?;
+                let bbox = vec![
+                    vec![rect.min().x, rect.min().y],
+                    vec![rect.max().x, rect.max().y],
+                ];
+                let zones = self
+                    .dggrs
+                    .zones_from_bbox(self.metadata.depth, &options, Some(bbox));
+                let values = zones
+                    .iter()
+                    .filter_map(|zone| Some((zone, *self.get(&zone.id)?)))
+                    .filter(|(zone, _)| match membership {
+                        Membership::Centroid => polygon.intersects(&zone.center),
+                        Membership::AreaFraction(fraction) => {
+                            let region = split_antimeridian(&zone.region);
+                            let inside = area_km2(&polygon.intersection(&region));
+                            inside > 0.0 && inside >= fraction * area_km2(&region)
+                        }
+                    })
+                    .map(|(_, value)| value)
+                    .collect();
+                ZonalStatistics::new(values)
+            })
+            .collect()
+    }
+}
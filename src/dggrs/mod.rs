@@ -8,3 +8,5 @@
 // except according to those terms.
 
 pub use crate::factory::dggrs_factory::get;
+
+pub mod resolution;
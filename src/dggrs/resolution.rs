@@ -0,0 +1,67 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Selection of a depth from a target zone size, based on the resolution statistics of a DGGRS.
+//! Lengths are in kilometres and areas in square kilometres. Targets that are not positive and
+//! finite have no closest depth, so the functions return `None` for them.
+
+use crate::models::common::ResolutionInfo;
+use crate::ports::dggrs::DggrsPort;
+
+/// Ground resolution of Web Mercator zoom level 0 at the equator in km per pixel,
+/// for 256 pixel tiles.
+pub const WEB_MERCATOR_KM_PER_PIXEL: f64 = 156.543_033_928;
+
+/// Depth whose mean zone area is closest to `area`.
+pub fn depth_for_area(dggrs: &dyn DggrsPort, area: f64) -> Option<u8> {
+    closest_depth(dggrs, area, |info| info.mean_area)
+}
+
+/// Depth whose zone edge length is closest to `length`, assuming regular hexagons.
+pub fn depth_for_edge_length(dggrs: &dyn DggrsPort, length: f64) -> Option<u8> {
+    closest_depth(dggrs, length, |info| {
+        (2.0 * info.mean_area / (3.0 * 3.0_f64.sqrt())).sqrt()
+    })
+}
+
+/// Depth whose spacing between zone centroids is closest to `spacing`.
+pub fn depth_for_spacing(dggrs: &dyn DggrsPort, spacing: f64) -> Option<u8> {
+    closest_depth(dggrs, spacing, |info| info.spacing)
+}
+
+/// Depth at which zones are about `pixels` wide on a Web Mercator map at the
+/// given zoom level and latitude.
+pub fn depth_for_zoom(dggrs: &dyn DggrsPort, zoom: u8, latitude: f64, pixels: f64) -> Option<u8> {
+    let km_per_pixel =
+        WEB_MERCATOR_KM_PER_PIXEL * latitude.to_radians().cos() / 2_f64.powi(zoom as i32);
+    depth_for_spacing(dggrs, pixels * km_per_pixel)
+}
+
+/// Depth of the DGGRS `to` whose mean zone area best matches that of `depth` in
+/// the DGGRS `from`, e.g. the IGEO7 depth equivalent to ISEA3H depth 9.
+pub fn equivalent_depth(from: &dyn DggrsPort, depth: u8, to: &dyn DggrsPort) -> Option<u8> {
+    depth_for_area(to, from.resolution_info(depth).mean_area)
+}
+
+/// Depth minimising the ratio between the target and the statistic, i.e. the
+/// closest depth on a logarithmic scale as zone sizes shrink geometrically.
+fn closest_depth<F>(dggrs: &dyn DggrsPort, target: f64, statistic: F) -> Option<u8>
+where
+    F: Fn(&ResolutionInfo) -> f64,
+{
+    if !(target.is_finite() && target > 0.0) {
+        return None;
+    }
+    dggrs
+        .resolution_table()
+        .iter()
+        .map(|info| (info.depth, (statistic(info) / target).ln().abs()))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(depth, _)| depth)
+}
@@ -0,0 +1,102 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use geo::orient::{Direction, Orient};
+use geo::{
+    Area, BooleanOps, BoundingRect, Centroid, Coord, LineString, MapCoords, MultiPolygon, Polygon,
+    Rect,
+};
+
+/// Splits a polygon crossing the antimeridian into parts within [-180, 180].
+/// DGGRID writes such zones with continuous longitudes beyond 180 (UNWRAP_EAST).
+pub fn split_antimeridian(polygon: &Polygon) -> MultiPolygon {
+    let unwrapped = Polygon::new(
+        unwrap_ring(polygon.exterior()),
+        polygon.interiors().iter().map(unwrap_ring).collect(),
+    );
+
+    let Some(rect) = unwrapped.bounding_rect() else {
+        return MultiPolygon(vec![]);
+    };
+    if rect.min().x >= -180.0 && rect.max().x <= 180.0 {
+        return MultiPolygon(vec![unwrapped]).orient(Direction::Default);
+    }
+
+    let mut parts = vec![];
+    for shift in [-360.0, 0.0, 360.0] {
+        let window = Rect::new(
+            Coord {
+                x: -180.0 - shift,
+                y: -90.0,
+            },
+            Coord {
+                x: 180.0 - shift,
+                y: 90.0,
+            },
+        )
+        .to_polygon();
+        for part in unwrapped.intersection(&window) {
+            parts.push(part.map_coords(|c| Coord {
+                x: c.x + shift,
+                y: c.y,
+            }));
+        }
+    }
+    MultiPolygon(parts).orient(Direction::Default)
+}
+
+/// Joins the parts of a zone split at the antimeridian into a single polygon,
+/// moving the western parts east of it.
+pub fn join_antimeridian(parts: &MultiPolygon) -> Polygon {
+    let east = parts
+        .iter()
+        .any(|p| p.exterior().coords().any(|c| c.x >= 180.0));
+    let west = parts
+        .iter()
+        .any(|p| p.exterior().coords().any(|c| c.x <= -180.0));
+
+    let mut joined = MultiPolygon(vec![]);
+    for part in parts {
+        let part = if east && west && part.centroid().is_some_and(|c| c.x() < 0.0) {
+            part.map_coords(|c| Coord {
+                x: c.x + 360.0,
+                y: c.y,
+            })
+        } else {
+            part.clone()
+        };
+        joined = joined.union(&MultiPolygon(vec![part]));
+    }
+
+    joined
+        .into_iter()
+        .max_by(|a, b| a.unsigned_area().total_cmp(&b.unsigned_area()))
+        .unwrap_or_else(|| Polygon::new(LineString::new(vec![]), vec![]))
+}
+
+/// Removes jumps of more than 180 degrees in longitude between consecutive vertices.
+fn unwrap_ring(ring: &LineString) -> LineString {
+    let mut offset = 0.0;
+    let mut previous: Option<f64> = None;
+    ring.coords()
+        .map(|c| {
+            if let Some(prev) = previous {
+                let delta = c.x + offset - prev;
+                if delta > 180.0 {
+                    offset -= 360.0;
+                } else if delta < -180.0 {
+                    offset += 360.0;
+                }
+            }
+            let x = c.x + offset;
+            previous = Some(x);
+            Coord { x, y: c.y }
+        })
+        .collect()
+}
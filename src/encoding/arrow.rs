@@ -0,0 +1,114 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::encoding::wkb;
+use crate::models::common::{Zone, ZoneID};
+use arrow_array::builder::{
+    BinaryBuilder, Float64Builder, ListBuilder, StringBuilder, UInt8Builder, UInt32Builder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use std::sync::Arc;
+
+/// Default number of zones per record batch.
+pub const BATCH_SIZE: usize = 65536;
+
+/// Arrow schema of zone record batches. The geometry column holds the zone
+/// polygon as WKB, split at the antimeridian into a multi-polygon where needed,
+/// and is null for zones queried without geometry.
+pub fn schema() -> SchemaRef {
+    let item = Arc::new(Field::new("item", DataType::Utf8, true));
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("depth", DataType::UInt8, true),
+        Field::new("center_lon", DataType::Float64, false),
+        Field::new("center_lat", DataType::Float64, false),
+        Field::new("vertex_count", DataType::UInt32, false),
+        Field::new("geometry", DataType::Binary, true),
+        Field::new("children", DataType::List(item.clone()), false),
+        Field::new("neighbors", DataType::List(item), false),
+    ]))
+}
+
+/// Converts zones into a single record batch.
+pub fn to_record_batch<'a, I>(zones: I) -> Result<RecordBatch, String>
+where
+    I: IntoIterator<Item = &'a Zone>,
+{
+    record_batch(
+        zones
+            .into_iter()
+            .map(|zone| (zone, wkb::region_to_wkb(zone))),
+    )
+}
+
+/// Converts zones, each with the WKB of its region, into a single record batch.
+pub(crate) fn record_batch<'a, I>(zones: I) -> Result<RecordBatch, String>
+where
+    I: IntoIterator<Item = (&'a Zone, Option<Vec<u8>>)>,
+{
+    let mut id = StringBuilder::new();
+    let mut depth = UInt8Builder::new();
+    let mut center_lon = Float64Builder::new();
+    let mut center_lat = Float64Builder::new();
+    let mut vertex_count = UInt32Builder::new();
+    let mut geometry = BinaryBuilder::new();
+    let mut children = ListBuilder::new(StringBuilder::new());
+    let mut neighbors = ListBuilder::new(StringBuilder::new());
+
+    for (zone, wkb) in zones {
+        id.append_value(&zone.id.id);
+        depth.append_option(zone.id.depth());
+        center_lon.append_value(zone.center.x());
+        center_lat.append_value(zone.center.y());
+        vertex_count.append_value(zone.vertex_count);
+        geometry.append_option(wkb);
+        append_ids(&mut children, &zone.children);
+        append_ids(&mut neighbors, &zone.neighbors);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(id.finish()),
+        Arc::new(depth.finish()),
+        Arc::new(center_lon.finish()),
+        Arc::new(center_lat.finish()),
+        Arc::new(vertex_count.finish()),
+        Arc::new(geometry.finish()),
+        Arc::new(children.finish()),
+        Arc::new(neighbors.finish()),
+    ];
+    RecordBatch::try_new(schema(), columns).map_err(|e| e.to_string())
+}
+
+/// Converts a stream of zones, e.g. from `zones_from_bbox_iter`, into record
+/// batches of at most `batch_size` zones.
+pub fn to_record_batches<I>(
+    zones: I,
+    batch_size: usize,
+) -> impl Iterator<Item = Result<RecordBatch, String>>
+where
+    I: IntoIterator<Item = Zone>,
+{
+    let mut zones = zones.into_iter();
+    std::iter::from_fn(move || {
+        let batch: Vec<Zone> = zones.by_ref().take(batch_size.max(1)).collect();
+        if batch.is_empty() {
+            None
+        } else {
+            Some(to_record_batch(&batch))
+        }
+    })
+}
+
+fn append_ids(builder: &mut ListBuilder<StringBuilder>, ids: &[ZoneID]) {
+    for id in ids {
+        builder.values().append_value(&id.id);
+    }
+    builder.append(true);
+}
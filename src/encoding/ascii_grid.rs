@@ -0,0 +1,52 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::raster::Raster;
+use std::io::{self, Write};
+
+/// Writes a raster as an ESRI ASCII grid. The format has no rotation terms,
+/// and rectangular pixels are written with the `dx` and `dy` header lines
+/// understood by GDAL instead of `cellsize`. Pixels without data are written
+/// as the raster nodata value, or -9999.
+pub fn write<W: Write>(raster: &Raster, writer: &mut W) -> io::Result<()> {
+    let gt = &raster.geotransform;
+    if gt[2] != 0.0 || gt[4] != 0.0 || gt[1] <= 0.0 || gt[5] >= 0.0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "ESRI ASCII grids must be north up without rotation",
+        ));
+    }
+    let nodata = raster.nodata.unwrap_or(-9999.0);
+
+    writeln!(writer, "ncols {}", raster.width)?;
+    writeln!(writer, "nrows {}", raster.height)?;
+    writeln!(writer, "xllcorner {}", gt[0])?;
+    writeln!(writer, "yllcorner {}", gt[3] + raster.height as f64 * gt[5])?;
+    if gt[1] == -gt[5] {
+        writeln!(writer, "cellsize {}", gt[1])?;
+    } else {
+        writeln!(writer, "dx {}", gt[1])?;
+        writeln!(writer, "dy {}", -gt[5])?;
+    }
+    writeln!(writer, "NODATA_value {}", nodata)?;
+
+    for row in 0..raster.height {
+        let line = (0..raster.width)
+            .map(|col| {
+                raster
+                    .get(col as i64, row as i64)
+                    .unwrap_or(nodata)
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(writer, "{}", line)?;
+    }
+    Ok(())
+}
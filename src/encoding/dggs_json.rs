@@ -0,0 +1,218 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::models::common::ZoneID;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// The values of a zone and its sub-zones encoded following the DGGS-JSON
+/// encoding of the OGC API for DGGS. Depths are relative to the zone, 0 being
+/// the zone itself. For each field and depth the data lists the values of the
+/// sub-zones in sub-zone order, with the additional dimensions varying fastest.
+/// The zone id is kept as given, so documents of other DGGRS implementations
+/// decode as well, see [`DggsJson::zone`] for the id of this crate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DggsJson {
+    #[serde(rename = "dggrs", alias = "dggrsId")]
+    pub dggrs_id: String,
+    pub zone_id: String,
+    pub depths: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dimensions: Vec<Dimension>,
+    pub values: BTreeMap<String, Vec<DepthValues>>,
+}
+
+/// An additional dimension of the data, e.g. time or a vertical level.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dimension {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<[serde_json::Value; 2]>,
+    pub grid: DimensionGrid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DimensionGrid {
+    pub cells_count: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coordinates: Vec<serde_json::Value>,
+}
+
+/// The values of one field at one relative depth.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepthValues {
+    pub depth: u8,
+    pub shape: Shape,
+    pub data: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Shape {
+    /// Total number of values.
+    pub count: usize,
+    pub sub_zones: usize,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dimensions: BTreeMap<String, usize>,
+}
+
+impl DggsJson {
+    pub fn new(dggrs_id: &str, zone_id: &str) -> Self {
+        Self {
+            dggrs_id: dggrs_id.to_string(),
+            zone_id: zone_id.to_string(),
+            depths: vec![],
+            dimensions: vec![],
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// The zone id as a [`ZoneID`], failing for ids not in the format of this crate.
+    pub fn zone(&self) -> Result<ZoneID, String> {
+        ZoneID::new(&self.zone_id)
+    }
+
+    /// Number of values per sub-zone, the product of the dimension sizes.
+    pub fn values_per_zone(&self) -> usize {
+        self.dimensions.iter().map(|d| d.grid.cells_count).product()
+    }
+
+    /// Adds the values of `field` at relative `depth`. `sub_zones` gives the
+    /// sub-zones in the sub-zone order of the DGGRS, e.g. as returned by
+    /// `zones_from_parent`. Every sub-zone holds one value per dimension cell,
+    /// sub-zones without values are encoded as nulls.
+    pub fn insert(
+        &mut self,
+        field: &str,
+        depth: u8,
+        sub_zones: &[ZoneID],
+        values: &HashMap<ZoneID, Vec<Option<f64>>>,
+    ) -> Result<(), String> {
+        let per_zone = self.values_per_zone();
+        let mut data = Vec::with_capacity(sub_zones.len() * per_zone);
+        for zone in sub_zones {
+            match values.get(zone) {
+                Some(v) if v.len() == per_zone => data.extend_from_slice(v),
+                Some(v) => {
+                    return Err(format!(
+                        "Zone {} has {} values, expected {}",
+                        zone,
+                        v.len(),
+                        per_zone
+                    ));
+                }
+                None => data.extend(std::iter::repeat_n(None, per_zone)),
+            }
+        }
+
+        let shape = Shape {
+            count: data.len(),
+            sub_zones: sub_zones.len(),
+            dimensions: self
+                .dimensions
+                .iter()
+                .map(|d| (d.name.clone(), d.grid.cells_count))
+                .collect(),
+        };
+
+        let entries = self.values.entry(field.to_string()).or_default();
+        entries.retain(|e| e.depth != depth);
+        entries.push(DepthValues { depth, shape, data });
+        entries.sort_by_key(|e| e.depth);
+
+        if !self.depths.contains(&depth) {
+            self.depths.push(depth);
+            self.depths.sort_unstable();
+        }
+        Ok(())
+    }
+
+    /// Adds a single value per sub-zone, for data without additional dimensions.
+    pub fn insert_scalar(
+        &mut self,
+        field: &str,
+        depth: u8,
+        sub_zones: &[ZoneID],
+        values: &HashMap<ZoneID, f64>,
+    ) -> Result<(), String> {
+        let values = values
+            .iter()
+            .map(|(id, v)| (id.clone(), vec![Some(*v)]))
+            .collect();
+        self.insert(field, depth, sub_zones, &values)
+    }
+
+    /// The values of `field` at relative `depth`, keyed by sub-zone. `sub_zones`
+    /// must list the sub-zones in the same order as used for encoding.
+    pub fn get(
+        &self,
+        field: &str,
+        depth: u8,
+        sub_zones: &[ZoneID],
+    ) -> Result<HashMap<ZoneID, Vec<Option<f64>>>, String> {
+        let entry = self
+            .values
+            .get(field)
+            .and_then(|entries| entries.iter().find(|e| e.depth == depth))
+            .ok_or(format!("No values for {} at depth {}", field, depth))?;
+
+        if entry.shape.sub_zones != sub_zones.len() {
+            return Err(format!(
+                "Expected {} sub-zones at depth {}, got {}",
+                entry.shape.sub_zones,
+                depth,
+                sub_zones.len()
+            ));
+        }
+        self.check(field, entry)?;
+        let per_zone = self.values_per_zone();
+
+        Ok(sub_zones
+            .iter()
+            .cloned()
+            .zip(entry.data.chunks(per_zone.max(1)).map(|c| c.to_vec()))
+            .collect())
+    }
+
+    pub fn to_json_string(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+
+    /// Decodes a document, failing when the values of a field do not hold one
+    /// value per sub-zone and dimension cell.
+    pub fn from_json_str(data: &str) -> Result<Self, String> {
+        let dggs_json: Self = serde_json::from_str(data).map_err(|e| e.to_string())?;
+        for (field, entries) in &dggs_json.values {
+            for entry in entries {
+                dggs_json.check(field, entry)?;
+            }
+        }
+        Ok(dggs_json)
+    }
+
+    /// Checks that the values of `field` at one depth match their shape.
+    fn check(&self, field: &str, entry: &DepthValues) -> Result<(), String> {
+        let expected = entry.shape.sub_zones * self.values_per_zone();
+        if entry.data.len() != expected || entry.shape.count != expected {
+            return Err(format!(
+                "{} at depth {} has {} values and a count of {}, expected {} sub-zones times {}",
+                field,
+                entry.depth,
+                entry.data.len(),
+                entry.shape.count,
+                entry.shape.sub_zones,
+                self.values_per_zone()
+            ));
+        }
+        Ok(())
+    }
+}
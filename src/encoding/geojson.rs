@@ -0,0 +1,158 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::encoding::antimeridian::{join_antimeridian, split_antimeridian};
+use crate::models::common::{Zone, ZoneID, Zones};
+use ::geojson::{Feature, FeatureCollection, GeoJson, Geometry, JsonObject, Value, feature::Id};
+use geo::{Centroid, LineString, MultiPolygon, Point, Polygon};
+use serde_json::json;
+use std::io::{self, Write};
+
+/// Converts zones into a GeoJSON `FeatureCollection`, one feature per zone. The
+/// feature id is the zone id, the properties hold the zone id, depth, DGGRS,
+/// vertex count, centroid, children and neighbours. Following RFC 7946, zones
+/// crossing the antimeridian are split into a `MultiPolygon` and exterior rings
+/// are counterclockwise.
+pub fn to_feature_collection(zones: &Zones, dggrs: &str) -> FeatureCollection {
+    FeatureCollection {
+        bbox: None,
+        features: zones.iter().map(|zone| to_feature(zone, dggrs)).collect(),
+        foreign_members: None,
+    }
+}
+
+/// Converts zones into a GeoJSON string, see [`to_feature_collection`].
+pub fn to_string(zones: &Zones, dggrs: &str) -> String {
+    to_feature_collection(zones, dggrs).to_string()
+}
+
+/// Writes a stream of zones, e.g. from `zones_from_bbox_iter`, as a GeoJSON
+/// `FeatureCollection`, one feature at a time, so neither the zones nor the
+/// complete document are held in memory.
+pub fn write<I, W>(zones: I, dggrs: &str, writer: &mut W) -> io::Result<()>
+where
+    I: IntoIterator<Item = Zone>,
+    W: Write,
+{
+    write!(writer, "{{\"type\":\"FeatureCollection\",\"features\":[")?;
+    for (i, zone) in zones.into_iter().enumerate() {
+        if i > 0 {
+            write!(writer, ",")?;
+        }
+        serde_json::to_writer(&mut *writer, &to_feature(&zone, dggrs))?;
+    }
+    write!(writer, "]}}")?;
+    Ok(())
+}
+
+/// Converts a single zone into a GeoJSON `Feature`.
+pub fn to_feature(zone: &Zone, dggrs: &str) -> Feature {
+    let mut properties = JsonObject::new();
+    properties.insert("zone_id".to_string(), json!(zone.id.id));
+    properties.insert("depth".to_string(), json!(zone.id.depth()));
+    properties.insert("dggrs".to_string(), json!(dggrs));
+    properties.insert("vertex_count".to_string(), json!(zone.vertex_count));
+    properties.insert(
+        "center".to_string(),
+        json!([zone.center.x(), zone.center.y()]),
+    );
+    properties.insert("children".to_string(), ids_to_json(&zone.children));
+    properties.insert("neighbors".to_string(), ids_to_json(&zone.neighbors));
+
+    let geometry = if zone.region.exterior().0.is_empty() {
+        None
+    } else {
+        let parts = split_antimeridian(&zone.region);
+        if parts.0.len() == 1 {
+            Some(Geometry::new(Value::from(&parts.0[0])))
+        } else {
+            Some(Geometry::new(Value::from(&parts)))
+        }
+    };
+
+    Feature {
+        bbox: None,
+        geometry,
+        id: Some(Id::String(zone.id.id.clone())),
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
+
+/// Reads zones from a GeoJSON `FeatureCollection` as written by [`to_string`].
+/// Zones split at the antimeridian are joined again, east of it.
+pub fn from_str(data: &str) -> Result<Zones, String> {
+    match data.parse::<GeoJson>().map_err(|e| e.to_string())? {
+        GeoJson::FeatureCollection(collection) => from_feature_collection(&collection),
+        _ => Err("GeoJSON must be a FeatureCollection".to_string()),
+    }
+}
+
+/// Reads zones from the features of a GeoJSON `FeatureCollection`.
+pub fn from_feature_collection(collection: &FeatureCollection) -> Result<Zones, String> {
+    collection.features.iter().map(from_feature).collect()
+}
+
+/// Reads a single zone from a GeoJSON `Feature`.
+pub fn from_feature(feature: &Feature) -> Result<Zone, String> {
+    let id = match (feature.property("zone_id"), &feature.id) {
+        (Some(serde_json::Value::String(id)), _) => ZoneID::new(id)?,
+        (_, Some(Id::String(id))) => ZoneID::new(id)?,
+        _ => return Err("Feature without a zone id".to_string()),
+    };
+
+    let region = match &feature.geometry {
+        Some(geometry) => match &geometry.value {
+            Value::Polygon(_) => {
+                Polygon::<f64>::try_from(&geometry.value).map_err(|e| e.to_string())?
+            }
+            Value::MultiPolygon(_) => join_antimeridian(
+                &MultiPolygon::<f64>::try_from(&geometry.value).map_err(|e| e.to_string())?,
+            ),
+            _ => return Err(format!("Zone {} is not a polygon", id)),
+        },
+        None => Polygon::new(LineString::new(vec![]), vec![]),
+    };
+
+    let center = match feature.property("center").and_then(|c| c.as_array()) {
+        Some(c) if c.len() == 2 => Point::new(
+            c[0].as_f64().ok_or("Invalid zone center")?,
+            c[1].as_f64().ok_or("Invalid zone center")?,
+        ),
+        _ => region.centroid().unwrap_or(Point::new(0.0, 0.0)),
+    };
+
+    let vertex_count = match feature.property("vertex_count").and_then(|v| v.as_u64()) {
+        Some(count) => count as u32,
+        None => region.exterior().0.len().saturating_sub(1) as u32,
+    };
+
+    Ok(Zone {
+        id,
+        region,
+        center,
+        vertex_count,
+        children: ids_from_json(feature.property("children"))?,
+        neighbors: ids_from_json(feature.property("neighbors"))?,
+    })
+}
+
+fn ids_to_json(ids: &[ZoneID]) -> serde_json::Value {
+    json!(ids.iter().map(|id| id.id.as_str()).collect::<Vec<_>>())
+}
+
+fn ids_from_json(value: Option<&serde_json::Value>) -> Result<Vec<ZoneID>, String> {
+    match value.and_then(|v| v.as_array()) {
+        Some(ids) => ids
+            .iter()
+            .map(|id| ZoneID::new(id.as_str().ok_or("Zone ids must be strings")?))
+            .collect(),
+        None => Ok(vec![]),
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::encoding::antimeridian::split_antimeridian;
+use crate::encoding::arrow::{record_batch, schema};
+use crate::encoding::wkb::parts_to_wkb;
+use crate::models::common::Zone;
+use geo::BoundingRect;
+use parquet::arrow::ArrowWriter;
+use parquet::format::KeyValue;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::io::Write;
+
+/// Writes zones to a GeoParquet 1.1 file with the columns of
+/// [`crate::encoding::arrow::schema`]. The `geo` metadata declares the WKB
+/// encoded `geometry` column as primary, in OGC:CRS84, with the geometry types
+/// and bounding box of the zones written. Zones are written in row groups of
+/// `batch_size`, so a stream of zones is never held in memory at once.
+pub fn write<W, I>(zones: I, writer: W, batch_size: usize) -> Result<(), String>
+where
+    W: Write + Send,
+    I: IntoIterator<Item = Zone>,
+{
+    let mut writer = ArrowWriter::try_new(writer, schema(), None).map_err(|e| e.to_string())?;
+    let mut geometry_types = BTreeSet::new();
+    let mut bbox = [
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    ];
+
+    let mut zones = zones.into_iter();
+    loop {
+        let batch: Vec<Zone> = zones.by_ref().take(batch_size.max(1)).collect();
+        if batch.is_empty() {
+            break;
+        }
+
+        // The split regions give both the WKB and the metadata
+        let mut geometries = Vec::with_capacity(batch.len());
+        for zone in &batch {
+            if zone.region.exterior().0.is_empty() {
+                geometries.push(None);
+                continue;
+            }
+            let parts = split_antimeridian(&zone.region);
+            geometry_types.insert(if parts.0.len() == 1 {
+                "Polygon"
+            } else {
+                "MultiPolygon"
+            });
+            if let Some(rect) = parts.bounding_rect() {
+                bbox[0] = bbox[0].min(rect.min().x);
+                bbox[1] = bbox[1].min(rect.min().y);
+                bbox[2] = bbox[2].max(rect.max().x);
+                bbox[3] = bbox[3].max(rect.max().y);
+            }
+            geometries.push(Some(parts_to_wkb(&parts, None)));
+        }
+
+        let record_batch = record_batch(batch.iter().zip(geometries))?;
+        writer.write(&record_batch).map_err(|e| e.to_string())?;
+    }
+
+    let mut column = json!({
+        "encoding": "WKB",
+        "geometry_types": geometry_types,
+        "edges": "planar",
+    });
+    if bbox[0].is_finite() {
+        column["bbox"] = json!(bbox);
+    }
+    let metadata = json!({
+        "version": "1.1.0",
+        "primary_column": "geometry",
+        "columns": { "geometry": column },
+    });
+    writer.append_key_value_metadata(KeyValue::new("geo".to_string(), metadata.to_string()));
+    writer.close().map_err(|e| e.to_string())?;
+    Ok(())
+}
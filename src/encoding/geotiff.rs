@@ -0,0 +1,157 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::coverage::raster::{Projection, Raster};
+use std::io::{self, Write};
+
+const TYPE_ASCII: u16 = 2;
+const TYPE_SHORT: u16 = 3;
+const TYPE_LONG: u16 = 4;
+const TYPE_DOUBLE: u16 = 12;
+
+/// A TIFF directory entry with its values already encoded in little endian.
+struct Entry {
+    tag: u16,
+    field_type: u16,
+    count: u32,
+    bytes: Vec<u8>,
+}
+
+impl Entry {
+    fn shorts(tag: u16, values: &[u16]) -> Self {
+        Self {
+            tag,
+            field_type: TYPE_SHORT,
+            count: values.len() as u32,
+            bytes: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+        }
+    }
+
+    fn long(tag: u16, value: u32) -> Self {
+        Self {
+            tag,
+            field_type: TYPE_LONG,
+            count: 1,
+            bytes: value.to_le_bytes().to_vec(),
+        }
+    }
+
+    fn doubles(tag: u16, values: &[f64]) -> Self {
+        Self {
+            tag,
+            field_type: TYPE_DOUBLE,
+            count: values.len() as u32,
+            bytes: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+        }
+    }
+
+    fn ascii(tag: u16, value: &str) -> Self {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        Self {
+            tag,
+            field_type: TYPE_ASCII,
+            count: bytes.len() as u32,
+            bytes,
+        }
+    }
+}
+
+/// Writes a raster as a single band, uncompressed, 64 bit floating point
+/// GeoTIFF in one strip. The georeferencing is given by a tie point and the
+/// pixel scale, hence the raster must be north up without rotation. The nodata
+/// value is recorded in the `GDAL_NODATA` tag. Offsets are 32 bit, so rasters
+/// beyond 4 GiB are refused.
+pub fn write<W: Write>(raster: &Raster, writer: &mut W) -> io::Result<()> {
+    let gt = &raster.geotransform;
+    if gt[2] != 0.0 || gt[4] != 0.0 || gt[1] <= 0.0 || gt[5] >= 0.0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Only north up rasters without rotation can be written as GeoTIFF",
+        ));
+    }
+
+    let data_len = raster.data.len() * 8;
+    // Geographic or projected model, pixels as areas, the EPSG code of the CRS
+    let geo_keys: [u16; 16] = match raster.projection {
+        Projection::Geographic => [1, 1, 0, 3, 1024, 0, 1, 2, 1025, 0, 1, 1, 2048, 0, 1, 4326],
+        Projection::WebMercator => [1, 1, 0, 3, 1024, 0, 1, 1, 1025, 0, 1, 1, 3072, 0, 1, 3857],
+    };
+
+    // Header, then the pixel data, then the directory
+    let data_offset = 8u32;
+    let mut entries = vec![
+        Entry::long(256, raster.width as u32),
+        Entry::long(257, raster.height as u32),
+        Entry::shorts(258, &[64]),
+        Entry::shorts(259, &[1]),
+        Entry::shorts(262, &[1]),
+        Entry::long(273, data_offset),
+        Entry::shorts(277, &[1]),
+        Entry::long(278, raster.height as u32),
+        Entry::long(279, data_len as u32),
+        Entry::shorts(284, &[1]),
+        Entry::shorts(339, &[3]),
+        Entry::doubles(33550, &[gt[1], -gt[5], 0.0]),
+        Entry::doubles(33922, &[0.0, 0.0, 0.0, gt[0], gt[3], 0.0]),
+        Entry::shorts(34735, &geo_keys),
+    ];
+    if let Some(nodata) = raster.nodata {
+        entries.push(Entry::ascii(42113, &nodata.to_string()));
+    }
+
+    let extra_len: usize = entries
+        .iter()
+        .filter(|entry| entry.bytes.len() > 4)
+        .map(|entry| entry.bytes.len() + entry.bytes.len() % 2)
+        .sum();
+    let file_len = data_offset as usize + data_len + data_len % 2 + 2 + 12 * entries.len() + 4;
+    if file_len + extra_len > u32::MAX as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The raster exceeds the 4 GiB of a GeoTIFF with 32 bit offsets",
+        ));
+    }
+
+    let data: Vec<u8> = raster.data.iter().flat_map(|v| v.to_le_bytes()).collect();
+    let mut ifd_offset = data_offset + data.len() as u32;
+    ifd_offset += ifd_offset % 2;
+    let mut extra_offset = ifd_offset + 2 + 12 * entries.len() as u32 + 4;
+
+    writer.write_all(b"II")?;
+    writer.write_all(&42u16.to_le_bytes())?;
+    writer.write_all(&ifd_offset.to_le_bytes())?;
+    writer.write_all(&data)?;
+    if data.len() % 2 == 1 {
+        writer.write_all(&[0])?;
+    }
+
+    writer.write_all(&(entries.len() as u16).to_le_bytes())?;
+    let mut extra = vec![];
+    for entry in &entries {
+        writer.write_all(&entry.tag.to_le_bytes())?;
+        writer.write_all(&entry.field_type.to_le_bytes())?;
+        writer.write_all(&entry.count.to_le_bytes())?;
+        if entry.bytes.len() <= 4 {
+            let mut value = entry.bytes.clone();
+            value.resize(4, 0);
+            writer.write_all(&value)?;
+        } else {
+            // Values too large for the entry are stored after the directory
+            writer.write_all(&extra_offset.to_le_bytes())?;
+            extra.extend_from_slice(&entry.bytes);
+            if entry.bytes.len() % 2 == 1 {
+                extra.push(0);
+            }
+            extra_offset = ifd_offset + 2 + 12 * entries.len() as u32 + 4 + extra.len() as u32;
+        }
+    }
+    writer.write_all(&0u32.to_le_bytes())?;
+    writer.write_all(&extra)
+}
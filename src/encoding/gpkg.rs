@@ -0,0 +1,324 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::encoding::antimeridian::split_antimeridian;
+use crate::encoding::wkb::{self, SRID_WGS84};
+use crate::models::common::{Zone, ZoneID};
+use geo::{BoundingRect, Rect};
+use rusqlite::{Connection, params};
+use std::path::Path;
+
+/// `GPKG` in ASCII, the SQLite application id of GeoPackage files.
+const APPLICATION_ID: i32 = 0x4750_4B47;
+/// GeoPackage version 1.4.0
+const USER_VERSION: i32 = 10400;
+const WGS84_WKT: &str = "GEOGCS[\"WGS 84\",DATUM[\"WGS_1984\",SPHEROID[\"WGS 84\",6378137,298.257223563,AUTHORITY[\"EPSG\",\"7030\"]],AUTHORITY[\"EPSG\",\"6326\"]],PRIMEM[\"Greenwich\",0,AUTHORITY[\"EPSG\",\"8901\"]],UNIT[\"degree\",0.0174532925199433,AUTHORITY[\"EPSG\",\"9122\"]],AUTHORITY[\"EPSG\",\"4326\"]]";
+
+/// Writes zones into the feature table `table` of the GeoPackage at `path`,
+/// creating the file if needed. Next to the geometry, the table holds the zone
+/// id, depth, vertex count, centroid, and the children and neighbours as space
+/// separated ids. The table gets an R-tree spatial index and a metadata record
+/// naming the DGGRS and the backend that generated the zones, e.g. `IGEO7` and
+/// `DGGRID`. Zones crossing the antimeridian are split into multi-polygons.
+pub fn write<I>(
+    zones: I,
+    path: &Path,
+    table: &str,
+    dggrs: &str,
+    backend: &str,
+) -> Result<(), String>
+where
+    I: IntoIterator<Item = Zone>,
+{
+    if table.is_empty() || !table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid GeoPackage table name: {}", table));
+    }
+
+    let mut conn = Connection::open(path).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute_batch(&format!(
+        "PRAGMA application_id = {APPLICATION_ID};
+         PRAGMA user_version = {USER_VERSION};"
+    ))
+    .map_err(|e| e.to_string())?;
+    create_core_tables(&tx).map_err(|e| e.to_string())?;
+
+    tx.execute_batch(&format!(
+        "CREATE TABLE \"{table}\" (
+            fid INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+            geom GEOMETRY,
+            zone_id TEXT NOT NULL UNIQUE,
+            depth INTEGER,
+            vertex_count INTEGER NOT NULL,
+            center_lon REAL NOT NULL,
+            center_lat REAL NOT NULL,
+            children TEXT NOT NULL,
+            neighbors TEXT NOT NULL
+        );
+        CREATE VIRTUAL TABLE \"rtree_{table}_geom\" USING rtree(id, minx, maxx, miny, maxy);"
+    ))
+    .map_err(|e| e.to_string())?;
+
+    let mut extent: Option<Rect> = None;
+    {
+        let mut insert = tx
+            .prepare(&format!(
+                "INSERT INTO \"{table}\" (geom, zone_id, depth, vertex_count, center_lon,
+                 center_lat, children, neighbors) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+            ))
+            .map_err(|e| e.to_string())?;
+        let mut index = tx
+            .prepare(&format!(
+                "INSERT INTO \"rtree_{table}_geom\" VALUES (?1, ?2, ?3, ?4, ?5)"
+            ))
+            .map_err(|e| e.to_string())?;
+
+        for zone in zones {
+            let geometry = geometry_blob(&zone);
+            insert
+                .execute(params![
+                    geometry.as_ref().map(|(blob, _)| blob),
+                    zone.id.id,
+                    zone.id.depth(),
+                    zone.vertex_count,
+                    zone.center.x(),
+                    zone.center.y(),
+                    join_ids(&zone.children),
+                    join_ids(&zone.neighbors),
+                ])
+                .map_err(|e| e.to_string())?;
+
+            if let Some((_, rect)) = geometry {
+                index
+                    .execute(params![
+                        tx.last_insert_rowid(),
+                        rect.min().x,
+                        rect.max().x,
+                        rect.min().y,
+                        rect.max().y
+                    ])
+                    .map_err(|e| e.to_string())?;
+                extent = Some(match extent {
+                    Some(e) => Rect::new(
+                        (e.min().x.min(rect.min().x), e.min().y.min(rect.min().y)),
+                        (e.max().x.max(rect.max().x), e.max().y.max(rect.max().y)),
+                    ),
+                    None => rect,
+                });
+            }
+        }
+    }
+
+    tx.execute(
+        "INSERT INTO gpkg_contents (table_name, data_type, identifier, description,
+         min_x, min_y, max_x, max_y, srs_id) VALUES (?1, 'features', ?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            table,
+            format!("{} zones generated with {}", dggrs, backend),
+            extent.map(|e| e.min().x),
+            extent.map(|e| e.min().y),
+            extent.map(|e| e.max().x),
+            extent.map(|e| e.max().y),
+            SRID_WGS84
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO gpkg_geometry_columns (table_name, column_name, geometry_type_name,
+         srs_id, z, m) VALUES (?1, 'geom', 'GEOMETRY', ?2, 0, 0)",
+        params![table, SRID_WGS84],
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.execute_batch(&rtree_triggers(table))
+        .map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO gpkg_extensions (table_name, column_name, extension_name, definition, scope)
+         VALUES (?1, 'geom', 'gpkg_rtree_index',
+         'http://www.geopackage.org/spec120/#extension_rtree', 'write-only')",
+        params![table],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let metadata = serde_json::json!({ "dggrs": dggrs, "backend": backend }).to_string();
+    tx.execute(
+        "INSERT INTO gpkg_metadata (md_scope, md_standard_uri, mime_type, metadata)
+         VALUES ('dataset', 'https://ogcapi.ogc.org/dggs/', 'application/json', ?1)",
+        params![metadata],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO gpkg_metadata_reference (reference_scope, table_name, md_file_id)
+         VALUES ('table', ?1, ?2)",
+        params![table, tx.last_insert_rowid()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// GeoPackage geometry blob of the zone region with its envelope, `None` when
+/// the region was not computed.
+fn geometry_blob(zone: &Zone) -> Option<(Vec<u8>, Rect)> {
+    if zone.region.exterior().0.is_empty() {
+        return None;
+    }
+    let parts = split_antimeridian(&zone.region);
+    let rect = parts.bounding_rect()?;
+    let wkb = if parts.0.len() == 1 {
+        wkb::polygon_to_wkb(&parts.0[0])
+    } else {
+        wkb::multi_polygon_to_wkb(&parts)
+    };
+
+    let mut blob = Vec::with_capacity(8 + 32 + wkb.len());
+    blob.extend_from_slice(b"GP");
+    blob.push(0); // version 1
+    blob.push(0b0000_0011); // little endian, [minx, maxx, miny, maxy] envelope
+    blob.extend_from_slice(&(SRID_WGS84 as i32).to_le_bytes());
+    for value in [rect.min().x, rect.max().x, rect.min().y, rect.max().y] {
+        blob.extend_from_slice(&value.to_le_bytes());
+    }
+    blob.extend_from_slice(&wkb);
+    Some((blob, rect))
+}
+
+fn join_ids(ids: &[ZoneID]) -> String {
+    ids.iter()
+        .map(|id| id.id.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn create_core_tables(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS gpkg_spatial_ref_sys (
+            srs_name TEXT NOT NULL,
+            srs_id INTEGER PRIMARY KEY,
+            organization TEXT NOT NULL,
+            organization_coordsys_id INTEGER NOT NULL,
+            definition TEXT NOT NULL,
+            description TEXT
+        );
+        CREATE TABLE IF NOT EXISTS gpkg_contents (
+            table_name TEXT NOT NULL PRIMARY KEY,
+            data_type TEXT NOT NULL,
+            identifier TEXT UNIQUE,
+            description TEXT DEFAULT '',
+            last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            min_x DOUBLE,
+            min_y DOUBLE,
+            max_x DOUBLE,
+            max_y DOUBLE,
+            srs_id INTEGER,
+            CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id)
+        );
+        CREATE TABLE IF NOT EXISTS gpkg_geometry_columns (
+            table_name TEXT NOT NULL,
+            column_name TEXT NOT NULL,
+            geometry_type_name TEXT NOT NULL,
+            srs_id INTEGER NOT NULL,
+            z TINYINT NOT NULL,
+            m TINYINT NOT NULL,
+            CONSTRAINT pk_geom_cols PRIMARY KEY (table_name, column_name),
+            CONSTRAINT fk_gc_tn FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name),
+            CONSTRAINT fk_gc_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys (srs_id)
+        );
+        CREATE TABLE IF NOT EXISTS gpkg_extensions (
+            table_name TEXT,
+            column_name TEXT,
+            extension_name TEXT NOT NULL,
+            definition TEXT NOT NULL,
+            scope TEXT NOT NULL,
+            CONSTRAINT ge_tce UNIQUE (table_name, column_name, extension_name)
+        );
+        CREATE TABLE IF NOT EXISTS gpkg_metadata (
+            id INTEGER CONSTRAINT m_pk PRIMARY KEY ASC NOT NULL,
+            md_scope TEXT NOT NULL DEFAULT 'dataset',
+            md_standard_uri TEXT NOT NULL,
+            mime_type TEXT NOT NULL DEFAULT 'text/xml',
+            metadata TEXT NOT NULL DEFAULT ''
+        );
+        CREATE TABLE IF NOT EXISTS gpkg_metadata_reference (
+            reference_scope TEXT NOT NULL,
+            table_name TEXT,
+            column_name TEXT,
+            row_id_value INTEGER,
+            timestamp DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            md_file_id INTEGER NOT NULL,
+            md_parent_id INTEGER,
+            CONSTRAINT crmr_mfi_fk FOREIGN KEY (md_file_id) REFERENCES gpkg_metadata(id),
+            CONSTRAINT crmr_mpi_fk FOREIGN KEY (md_parent_id) REFERENCES gpkg_metadata(id)
+        );
+        -- NULL column names never conflict on the unique constraint
+        INSERT INTO gpkg_extensions
+            SELECT 'gpkg_metadata', NULL, 'gpkg_metadata',
+                'http://www.geopackage.org/spec/#extension_metadata', 'read-write'
+            WHERE NOT EXISTS (SELECT 1 FROM gpkg_extensions
+                WHERE table_name = 'gpkg_metadata' AND extension_name = 'gpkg_metadata');
+        INSERT INTO gpkg_extensions
+            SELECT 'gpkg_metadata_reference', NULL, 'gpkg_metadata',
+                'http://www.geopackage.org/spec/#extension_metadata', 'read-write'
+            WHERE NOT EXISTS (SELECT 1 FROM gpkg_extensions
+                WHERE table_name = 'gpkg_metadata_reference' AND extension_name = 'gpkg_metadata');",
+    )?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES
+            ('WGS 84 geodetic', 4326, 'EPSG', 4326, ?1,
+             'longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid'),
+            ('Undefined cartesian SRS', -1, 'NONE', -1, 'undefined',
+             'undefined cartesian coordinate reference system'),
+            ('Undefined geographic SRS', 0, 'NONE', 0, 'undefined',
+             'undefined geographic coordinate reference system')",
+        params![WGS84_WKT],
+    )?;
+    Ok(())
+}
+
+/// Triggers of the GeoPackage R-tree extension keeping the index up to date.
+/// They rely on the ST_ functions provided by GeoPackage aware clients.
+fn rtree_triggers(table: &str) -> String {
+    let rtree = format!("rtree_{table}_geom");
+    let values =
+        "NEW.fid, ST_MinX(NEW.geom), ST_MaxX(NEW.geom), ST_MinY(NEW.geom), ST_MaxY(NEW.geom)";
+    format!(
+        "CREATE TRIGGER \"{rtree}_insert\" AFTER INSERT ON \"{table}\"
+           WHEN (NEW.geom NOT NULL AND NOT ST_IsEmpty(NEW.geom))
+         BEGIN
+           INSERT OR REPLACE INTO \"{rtree}\" VALUES ({values});
+         END;
+         CREATE TRIGGER \"{rtree}_update1\" AFTER UPDATE OF geom ON \"{table}\"
+           WHEN OLD.fid = NEW.fid AND (NEW.geom NOTNULL AND NOT ST_IsEmpty(NEW.geom))
+         BEGIN
+           INSERT OR REPLACE INTO \"{rtree}\" VALUES ({values});
+         END;
+         CREATE TRIGGER \"{rtree}_update2\" AFTER UPDATE OF geom ON \"{table}\"
+           WHEN OLD.fid = NEW.fid AND (NEW.geom ISNULL OR ST_IsEmpty(NEW.geom))
+         BEGIN
+           DELETE FROM \"{rtree}\" WHERE id = OLD.fid;
+         END;
+         CREATE TRIGGER \"{rtree}_update3\" AFTER UPDATE ON \"{table}\"
+           WHEN OLD.fid != NEW.fid AND (NEW.geom NOTNULL AND NOT ST_IsEmpty(NEW.geom))
+         BEGIN
+           DELETE FROM \"{rtree}\" WHERE id = OLD.fid;
+           INSERT OR REPLACE INTO \"{rtree}\" VALUES ({values});
+         END;
+         CREATE TRIGGER \"{rtree}_update4\" AFTER UPDATE ON \"{table}\"
+           WHEN OLD.fid != NEW.fid AND (NEW.geom ISNULL OR ST_IsEmpty(NEW.geom))
+         BEGIN
+           DELETE FROM \"{rtree}\" WHERE id IN (OLD.fid, NEW.fid);
+         END;
+         CREATE TRIGGER \"{rtree}_delete\" AFTER DELETE ON \"{table}\"
+           WHEN OLD.geom NOT NULL
+         BEGIN
+           DELETE FROM \"{rtree}\" WHERE id = OLD.fid;
+         END;"
+    )
+}
@@ -0,0 +1,24 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub mod antimeridian;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod ascii_grid;
+#[cfg(feature = "dggs-json")]
+pub mod dggs_json;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+#[cfg(feature = "geoparquet")]
+pub mod geoparquet;
+pub mod geotiff;
+#[cfg(feature = "gpkg")]
+pub mod gpkg;
+pub mod wkb;
+pub mod wkt;
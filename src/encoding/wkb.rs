@@ -0,0 +1,140 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::encoding::antimeridian::split_antimeridian;
+use crate::models::common::Zone;
+use geo::{LineString, MultiPolygon, Point, Polygon};
+
+/// SRID of WGS84 geographic coordinates, written into EWKB.
+pub const SRID_WGS84: u32 = 4326;
+
+const WKB_POINT: u32 = 1;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTIPOLYGON: u32 = 6;
+const EWKB_SRID_FLAG: u32 = 0x2000_0000;
+
+/// Little endian WKB of the zone region, split at the antimeridian into a
+/// multi-polygon where needed. `None` when the region was not computed.
+pub fn region_to_wkb(zone: &Zone) -> Option<Vec<u8>> {
+    region(zone, None)
+}
+
+/// Little endian EWKB of the zone region with SRID 4326, see [`region_to_wkb`].
+pub fn region_to_ewkb(zone: &Zone) -> Option<Vec<u8>> {
+    region(zone, Some(SRID_WGS84))
+}
+
+/// Little endian WKB of the zone centroid.
+pub fn center_to_wkb(zone: &Zone) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(21);
+    write_point(&mut buf, &zone.center, None);
+    buf
+}
+
+/// Little endian EWKB of the zone centroid with SRID 4326.
+pub fn center_to_ewkb(zone: &Zone) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(25);
+    write_point(&mut buf, &zone.center, Some(SRID_WGS84));
+    buf
+}
+
+/// Little endian WKB of a point.
+pub fn point_to_wkb(point: &Point) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(21);
+    write_point(&mut buf, point, None);
+    buf
+}
+
+/// Little endian WKB of a polygon.
+pub fn polygon_to_wkb(polygon: &Polygon) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_polygon(&mut buf, polygon, None);
+    buf
+}
+
+/// Little endian WKB of a multi-polygon.
+pub fn multi_polygon_to_wkb(multi_polygon: &MultiPolygon) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_multi_polygon(&mut buf, multi_polygon, None);
+    buf
+}
+
+fn region(zone: &Zone, srid: Option<u32>) -> Option<Vec<u8>> {
+    if zone.region.exterior().0.is_empty() {
+        return None;
+    }
+    Some(parts_to_wkb(&split_antimeridian(&zone.region), srid))
+}
+
+/// WKB of the parts of a region split at the antimeridian, a polygon when there
+/// is a single part.
+pub(crate) fn parts_to_wkb(parts: &MultiPolygon, srid: Option<u32>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    if parts.0.len() == 1 {
+        write_polygon(&mut buf, &parts.0[0], srid);
+    } else {
+        write_multi_polygon(&mut buf, parts, srid);
+    }
+    buf
+}
+
+fn write_point(buf: &mut Vec<u8>, point: &Point, srid: Option<u32>) {
+    write_header(buf, WKB_POINT, srid);
+    write_f64(buf, point.x());
+    write_f64(buf, point.y());
+}
+
+fn write_multi_polygon(buf: &mut Vec<u8>, multi_polygon: &MultiPolygon, srid: Option<u32>) {
+    write_header(buf, WKB_MULTIPOLYGON, srid);
+    write_u32(buf, multi_polygon.0.len() as u32);
+    for polygon in multi_polygon {
+        // only the outer geometry carries the SRID
+        write_polygon(buf, polygon, None);
+    }
+}
+
+fn write_polygon(buf: &mut Vec<u8>, polygon: &Polygon, srid: Option<u32>) {
+    write_header(buf, WKB_POLYGON, srid);
+    if polygon.exterior().0.is_empty() {
+        write_u32(buf, 0);
+        return;
+    }
+    write_u32(buf, 1 + polygon.interiors().len() as u32);
+    write_ring(buf, polygon.exterior());
+    for ring in polygon.interiors() {
+        write_ring(buf, ring);
+    }
+}
+
+fn write_ring(buf: &mut Vec<u8>, ring: &LineString) {
+    write_u32(buf, ring.0.len() as u32);
+    for coord in ring.coords() {
+        write_f64(buf, coord.x);
+        write_f64(buf, coord.y);
+    }
+}
+
+fn write_header(buf: &mut Vec<u8>, geometry_type: u32, srid: Option<u32>) {
+    buf.push(1); // little endian
+    match srid {
+        Some(srid) => {
+            write_u32(buf, geometry_type | EWKB_SRID_FLAG);
+            write_u32(buf, srid);
+        }
+        None => write_u32(buf, geometry_type),
+    }
+}
+
+fn write_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_f64(buf: &mut Vec<u8>, value: f64) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
@@ -0,0 +1,80 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::encoding::antimeridian::split_antimeridian;
+use crate::models::common::{Zone, Zones};
+use geo::{LineString, MultiPolygon, Point, Polygon};
+use std::io::{self, Write};
+
+/// WKT of the zone region, split at the antimeridian into a multi-polygon
+/// where needed. `None` when the region was not computed.
+pub fn region_to_wkt(zone: &Zone) -> Option<String> {
+    if zone.region.exterior().0.is_empty() {
+        return None;
+    }
+    let parts = split_antimeridian(&zone.region);
+    if parts.0.len() == 1 {
+        Some(polygon_to_wkt(&parts.0[0]))
+    } else {
+        Some(multi_polygon_to_wkt(&parts))
+    }
+}
+
+/// WKT of the zone centroid.
+pub fn center_to_wkt(zone: &Zone) -> String {
+    point_to_wkt(&zone.center)
+}
+
+pub fn point_to_wkt(point: &Point) -> String {
+    format!("POINT({} {})", point.x(), point.y())
+}
+
+pub fn polygon_to_wkt(polygon: &Polygon) -> String {
+    if polygon.exterior().0.is_empty() {
+        return "POLYGON EMPTY".to_string();
+    }
+    format!("POLYGON{}", polygon_text(polygon))
+}
+
+pub fn multi_polygon_to_wkt(multi_polygon: &MultiPolygon) -> String {
+    if multi_polygon.0.is_empty() {
+        return "MULTIPOLYGON EMPTY".to_string();
+    }
+    let polygons: Vec<String> = multi_polygon.iter().map(polygon_text).collect();
+    format!("MULTIPOLYGON({})", polygons.join(","))
+}
+
+/// Writes zones as tab separated lines of zone id, region WKT and centroid WKT,
+/// suitable for a bulk `COPY` into a spatial database. Missing regions are
+/// written as `\N`, the null marker of the PostgreSQL text format.
+pub fn write_tsv<W: Write>(zones: &Zones, writer: &mut W) -> io::Result<()> {
+    for zone in zones {
+        writeln!(
+            writer,
+            "{}\t{}\t{}",
+            zone.id,
+            region_to_wkt(zone).unwrap_or_else(|| "\\N".to_string()),
+            center_to_wkt(zone)
+        )?;
+    }
+    Ok(())
+}
+
+fn polygon_text(polygon: &Polygon) -> String {
+    let rings: Vec<String> = std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .map(ring_text)
+        .collect();
+    format!("({})", rings.join(","))
+}
+
+fn ring_text(ring: &LineString) -> String {
+    let coords: Vec<String> = ring.coords().map(|c| format!("{} {}", c.x, c.y)).collect();
+    format!("({})", coords.join(","))
+}
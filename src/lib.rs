@@ -9,10 +9,14 @@
 
 #![doc = include_str!("../README.md")]
 pub mod adapters;
+pub mod coverage;
 pub mod dggrs;
+pub mod encoding;
 pub mod factory;
 pub mod models;
 pub mod ports;
+pub mod types;
+pub mod vector;
 
 /// This is the only re-export that is needed.
 pub use factory::dggrs_factory::get;
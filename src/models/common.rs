@@ -8,24 +8,139 @@
 // except according to those terms.
 
 use geo::{Point, Polygon};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
-#[derive(Debug)]
+/// Radius of the authalic sphere of WGS84 in km, as used by DGGRID
+pub const EARTH_RADIUS_KM: f64 = 6371.007180918475;
+
+#[derive(Debug, Clone)]
 pub struct Zone {
     pub id: ZoneID,
     pub region: Polygon,
     pub center: Point,
     pub vertex_count: u32,
-    pub children: Option<Vec<String>>,
-    pub neighbors: Option<Vec<String>>,
+    /// Zones at the next depth, empty when not requested.
+    pub children: Vec<ZoneID>,
+    /// Zones sharing an edge or vertex, empty when not requested.
+    pub neighbors: Vec<ZoneID>,
 }
 
-#[derive(Debug)]
+/// A collection of zones in insertion order, indexed by zone id.
+#[derive(Debug, Clone, Default)]
 pub struct Zones {
-    pub zones: Vec<Zone>,
+    zones: Vec<Zone>,
+    index: HashMap<ZoneID, usize>,
 }
 
-#[derive(Debug, Clone)]
+impl Zones {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a zone. A zone whose id is already present is kept as well, but
+    /// lookups return the first one until the collection is deduplicated.
+    pub fn push(&mut self, zone: Zone) {
+        self.index
+            .entry(zone.id.clone())
+            .or_insert(self.zones.len());
+        self.zones.push(zone);
+    }
+
+    pub fn get(&self, id: &ZoneID) -> Option<&Zone> {
+        self.index.get(id).map(|&i| &self.zones[i])
+    }
+
+    pub fn contains(&self, id: &ZoneID) -> bool {
+        self.index.contains_key(id)
+    }
+
+    /// Appends the zones of `other` whose id is not yet present.
+    pub fn merge(&mut self, other: Zones) {
+        for zone in other {
+            if !self.contains(&zone.id) {
+                self.push(zone);
+            }
+        }
+    }
+
+    /// Removes zones with repeated ids, keeping the first of each.
+    pub fn dedup(&mut self) {
+        let zones = std::mem::take(&mut self.zones);
+        self.index.clear();
+        for zone in zones {
+            if !self.contains(&zone.id) {
+                self.push(zone);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.zones.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Zone> {
+        self.zones.iter()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &ZoneID> {
+        self.zones.iter().map(|zone| &zone.id)
+    }
+
+    pub fn as_slice(&self) -> &[Zone] {
+        &self.zones
+    }
+
+    pub fn into_vec(self) -> Vec<Zone> {
+        self.zones
+    }
+}
+
+impl From<Vec<Zone>> for Zones {
+    fn from(zones: Vec<Zone>) -> Self {
+        zones.into_iter().collect()
+    }
+}
+
+impl FromIterator<Zone> for Zones {
+    fn from_iter<I: IntoIterator<Item = Zone>>(iter: I) -> Self {
+        let mut zones = Zones::new();
+        zones.extend(iter);
+        zones
+    }
+}
+
+impl Extend<Zone> for Zones {
+    fn extend<I: IntoIterator<Item = Zone>>(&mut self, iter: I) {
+        for zone in iter {
+            self.push(zone);
+        }
+    }
+}
+
+impl IntoIterator for Zones {
+    type Item = Zone;
+    type IntoIter = std::vec::IntoIter<Zone>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.zones.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Zones {
+    type Item = &'a Zone;
+    type IntoIter = std::slice::Iter<'a, Zone>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.zones.iter()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ZoneID {
     pub id: String,
 }
@@ -38,6 +153,16 @@ impl ZoneID {
             Err("ID must be exactly 16 or 18 alphanumeric characters.".to_string())
         }
     }
+
+    /// The depth given by the two leading digits of an 18 character id, as
+    /// prepended by the DGGRID adapters.
+    pub fn depth(&self) -> Option<u8> {
+        if self.id.len() == 18 {
+            self.id[..2].parse::<u8>().ok()
+        } else {
+            None
+        }
+    }
 }
 
 impl Default for ZoneID {
@@ -53,3 +178,155 @@ impl fmt::Display for ZoneID {
         write!(f, "{}", self.id)
     }
 }
+
+/// Controls what a zone query computes. Every element that is not requested is
+/// skipped by the backend, so e.g. a centroid-only query does not pay for the
+/// zone polygons or the topology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoneQueryOptions {
+    /// Compute the zone polygon (`Zone::region`). Without it the region is left empty.
+    pub geometry: bool,
+    /// Number of points added along each edge of the zone polygon, 0 keeps the plain vertices.
+    /// Defaults to 50, as the polygons were always densified before these options existed.
+    pub densification: u8,
+    /// Compute the children of each zone (`Zone::children`).
+    pub children: bool,
+    /// Compute the neighbours of each zone (`Zone::neighbors`).
+    pub neighbors: bool,
+    /// Only compute the zone id and centroid, overrides all of the above.
+    pub centroid_only: bool,
+    /// Number of decimal places of the output coordinates.
+    pub precision: u8,
+}
+
+impl ZoneQueryOptions {
+    /// Options for a query returning only the zone ids and centroids.
+    pub fn centroid_only() -> Self {
+        Self {
+            centroid_only: true,
+            ..Self::default()
+        }
+    }
+
+    /// Options for a query returning the zone polygons densified with `points` per edge.
+    pub fn densified(points: u8) -> Self {
+        Self {
+            densification: points,
+            ..Self::default()
+        }
+    }
+
+    pub fn wants_geometry(&self) -> bool {
+        self.geometry && !self.centroid_only
+    }
+
+    pub fn wants_children(&self) -> bool {
+        self.children && !self.centroid_only
+    }
+
+    pub fn wants_neighbors(&self) -> bool {
+        self.neighbors && !self.centroid_only
+    }
+}
+
+impl Default for ZoneQueryOptions {
+    fn default() -> Self {
+        Self {
+            geometry: true,
+            densification: 50,
+            children: true,
+            neighbors: true,
+            centroid_only: false,
+            precision: 9,
+        }
+    }
+}
+
+/// Statistics of a single depth (refinement level) of a DGGRS. Lengths are
+/// expressed in kilometres and areas in square kilometres.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolutionInfo {
+    pub depth: u8,
+    /// Number of zones covering the globe at this depth.
+    pub zone_count: u64,
+    /// Mean zone area.
+    pub mean_area: f64,
+    /// Mean distance between the centroids of neighbouring zones.
+    pub spacing: f64,
+    /// Characteristic length scale, the diameter of a spherical cap with the mean zone area.
+    pub cls: f64,
+}
+
+/// Statistic of the points falling in a zone, see [`crate::ports::dggrs::DggrsPort::bin_points`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointBinning {
+    /// Number of points.
+    Count,
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// 1 for every zone with at least one point.
+    Presence,
+}
+
+impl PointBinning {
+    /// Whether the statistic depends on the values of the points, or only on their location.
+    pub fn uses_values(&self) -> bool {
+        !matches!(self, PointBinning::Count | PointBinning::Presence)
+    }
+
+    /// Bins the values of points by the ids of the zones containing them, skipping the points
+    /// without zone. The values are ignored for `Count` and `Presence`, the other statistics
+    /// fail unless there is one value per point.
+    pub fn bin(
+        &self,
+        ids: &[Option<ZoneID>],
+        values: &[f64],
+    ) -> Result<BTreeMap<ZoneID, f64>, String> {
+        self.check_values(ids.len(), values)?;
+        // Per zone, the count, sum, minimum and maximum of the values
+        let mut bins: BTreeMap<ZoneID, (usize, f64, f64, f64)> = BTreeMap::new();
+        for (i, id) in ids.iter().enumerate() {
+            let Some(id) = id else {
+                continue;
+            };
+            let value = if self.uses_values() { values[i] } else { 0.0 };
+            let bin = bins
+                .entry(id.clone())
+                .or_insert((0, 0.0, f64::INFINITY, f64::NEG_INFINITY));
+            bin.0 += 1;
+            bin.1 += value;
+            bin.2 = bin.2.min(value);
+            bin.3 = bin.3.max(value);
+        }
+
+        Ok(bins
+            .into_iter()
+            .map(|(id, (count, sum, min, max))| {
+                let value = match self {
+                    PointBinning::Count => count as f64,
+                    PointBinning::Sum => sum,
+                    PointBinning::Mean => sum / count as f64,
+                    PointBinning::Min => min,
+                    PointBinning::Max => max,
+                    PointBinning::Presence => 1.0,
+                };
+                (id, value)
+            })
+            .collect())
+    }
+
+    /// Fails when the statistic uses values and there is not one value for each of the
+    /// `points`.
+    pub fn check_values(&self, points: usize, values: &[f64]) -> Result<(), String> {
+        if self.uses_values() && values.len() != points {
+            return Err(format!(
+                "{} values given for {} points",
+                values.len(),
+                points
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::models::common::Zones;
+use geo::{Area, Contains, Coord, LineString, MultiPolygon, Point, Polygon, Winding};
+use std::collections::{HashMap, HashSet};
+
+/// Vertices closer than this many degrees are considered the same, matching the
+/// default coordinate precision of the DGGRID adapters.
+const VERTEX_TOLERANCE: f64 = 1e-9;
+
+type VertexKey = (i64, i64);
+
+impl Zones {
+    /// Merges the regions of adjacent zones into the outline of the whole
+    /// collection. Zones tile the sphere, so two adjacent zones share their common
+    /// edge vertex by vertex and the outline is made of the edges not shared with
+    /// another zone of the collection. With the neighbour topology, zones whose
+    /// neighbours are all part of the collection are skipped altogether and the
+    /// edges of the others are only matched against their neighbours, which keeps
+    /// large selections fast. The topology is only used when every zone carries
+    /// its neighbours, otherwise all edges are matched against each other.
+    ///
+    /// Coordinates are compared as given, zones on either side of the antimeridian
+    /// only merge when they share the same longitude range.
+    pub fn dissolve(&self) -> MultiPolygon {
+        let mut edges: HashMap<(VertexKey, VertexKey), (Coord, Coord)> = HashMap::new();
+
+        if self.iter().any(|zone| zone.neighbors.is_empty()) {
+            // Without topology, an edge shared with an adjacent zone cancels out as
+            // it is walked the other way round there
+            for zone in self.iter() {
+                for (a, b, start, end) in ring_edges(&zone.region) {
+                    if edges.remove(&(b, a)).is_none() {
+                        edges.insert((a, b), (start, end));
+                    }
+                }
+            }
+        } else {
+            for zone in self.iter() {
+                let members: Vec<&Polygon> = zone
+                    .neighbors
+                    .iter()
+                    .filter_map(|id| self.get(id))
+                    .map(|neighbor| &neighbor.region)
+                    .collect();
+                if members.len() == zone.neighbors.len() {
+                    continue;
+                }
+
+                let shared: HashSet<(VertexKey, VertexKey)> = members
+                    .into_iter()
+                    .flat_map(ring_edges)
+                    .map(|(a, b, _, _)| (b, a))
+                    .collect();
+                for (a, b, start, end) in ring_edges(&zone.region) {
+                    if !shared.contains(&(a, b)) {
+                        edges.insert((a, b), (start, end));
+                    }
+                }
+            }
+        }
+
+        let (shells, holes) = chain_rings(edges);
+        assemble(shells, holes)
+    }
+}
+
+/// The edges of the exterior ring in counter-clockwise order, with their vertex keys.
+fn ring_edges(region: &Polygon) -> Vec<(VertexKey, VertexKey, Coord, Coord)> {
+    let ring = region.exterior();
+    if ring.0.len() < 4 {
+        return vec![];
+    }
+    let mut coords: Vec<Coord> = ring.0.clone();
+    if !ring.is_ccw() {
+        coords.reverse();
+    }
+    coords
+        .windows(2)
+        .map(|pair| (key(pair[0]), key(pair[1]), pair[0], pair[1]))
+        .filter(|(a, b, _, _)| a != b)
+        .collect()
+}
+
+fn key(coord: Coord) -> VertexKey {
+    (
+        (coord.x / VERTEX_TOLERANCE).round() as i64,
+        (coord.y / VERTEX_TOLERANCE).round() as i64,
+    )
+}
+
+/// Links the outline edges into closed rings, split into counter-clockwise shells
+/// and clockwise holes. Where rings touch at a single vertex, the ring follows the
+/// sharpest left turn, which keeps the area it encloses on its left and the
+/// touching rings apart.
+fn chain_rings(
+    edges: HashMap<(VertexKey, VertexKey), (Coord, Coord)>,
+) -> (Vec<LineString>, Vec<LineString>) {
+    let mut outgoing: HashMap<VertexKey, Vec<(VertexKey, Coord, Coord)>> = HashMap::new();
+    for ((a, b), (start, end)) in edges {
+        outgoing.entry(a).or_default().push((b, start, end));
+    }
+    // Start from the smallest vertex to keep the output deterministic
+    let mut starts: Vec<VertexKey> = outgoing.keys().copied().collect();
+    starts.sort_unstable();
+
+    let mut shells = vec![];
+    let mut holes = vec![];
+    for first in starts {
+        while let Some(edge) = pop_edge(&mut outgoing, first, None) {
+            let (mut next, start, mut end) = edge;
+            let mut incoming = direction(start, end);
+            let mut coords = vec![start];
+            loop {
+                coords.push(end);
+                // Back at the start, the ring closes unless another edge there turns
+                // further left than the one it started with
+                if next == first && !turns_left_of(&outgoing, first, incoming, &edge) {
+                    break;
+                }
+                match pop_edge(&mut outgoing, next, Some(incoming)) {
+                    Some((to, from_coord, to_coord)) => {
+                        next = to;
+                        incoming = direction(from_coord, to_coord);
+                        end = to_coord;
+                    }
+                    None => break,
+                }
+            }
+            if next == first {
+                coords.pop();
+            }
+            coords.push(start);
+
+            if coords.len() < 4 {
+                continue;
+            }
+            let ring = LineString::new(coords);
+            if ring.is_ccw() {
+                shells.push(ring);
+            } else {
+                holes.push(ring);
+            }
+        }
+    }
+    (shells, holes)
+}
+
+fn direction(start: Coord, end: Coord) -> Coord {
+    end - start
+}
+
+/// Signed angle from the incoming to the outgoing direction, positive to the left.
+fn turn(incoming: Coord, outgoing: Coord) -> f64 {
+    let cross = incoming.x * outgoing.y - incoming.y * outgoing.x;
+    let dot = incoming.x * outgoing.x + incoming.y * outgoing.y;
+    cross.atan2(dot)
+}
+
+/// Whether an edge left at the vertex turns further left than the given edge.
+fn turns_left_of(
+    outgoing: &HashMap<VertexKey, Vec<(VertexKey, Coord, Coord)>>,
+    from: VertexKey,
+    incoming: Coord,
+    edge: &(VertexKey, Coord, Coord),
+) -> bool {
+    let angle = turn(incoming, direction(edge.1, edge.2));
+    outgoing.get(&from).is_some_and(|list| {
+        list.iter()
+            .any(|(_, start, end)| turn(incoming, direction(*start, *end)) > angle)
+    })
+}
+
+/// Takes an edge leaving the vertex, the sharpest left turn from the incoming
+/// direction when there is a choice.
+fn pop_edge(
+    outgoing: &mut HashMap<VertexKey, Vec<(VertexKey, Coord, Coord)>>,
+    from: VertexKey,
+    incoming: Option<Coord>,
+) -> Option<(VertexKey, Coord, Coord)> {
+    let list = outgoing.get_mut(&from)?;
+    let index = match incoming {
+        Some(incoming) => list
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| {
+                turn(incoming, direction(a.1, a.2)).total_cmp(&turn(incoming, direction(b.1, b.2)))
+            })
+            .map(|(i, _)| i)?,
+        None => list.len().checked_sub(1)?,
+    };
+    let edge = list.swap_remove(index);
+    if list.is_empty() {
+        outgoing.remove(&from);
+    }
+    Some(edge)
+}
+
+/// Assigns every hole to the smallest shell containing it.
+fn assemble(shells: Vec<LineString>, holes: Vec<LineString>) -> MultiPolygon {
+    let mut polygons: Vec<Polygon> = shells
+        .into_iter()
+        .map(|shell| Polygon::new(shell, vec![]))
+        .collect();
+    let areas: Vec<f64> = polygons.iter().map(|p| p.unsigned_area()).collect();
+
+    for hole in holes {
+        let probe = Point::from(hole.0[0]);
+        let owner = polygons
+            .iter()
+            .enumerate()
+            .filter(|(_, polygon)| polygon.contains(&probe))
+            .min_by(|(a, _), (b, _)| areas[*a].total_cmp(&areas[*b]))
+            .map(|(i, _)| i);
+        match owner {
+            Some(i) => polygons[i].interiors_push(hole),
+            // A hole without shell only happens with a broken outline, keep its area anyway
+            None => {
+                let mut shell = hole;
+                shell.0.reverse();
+                polygons.push(Polygon::new(shell, vec![]));
+            }
+        }
+    }
+    MultiPolygon(polygons)
+}
@@ -8,4 +8,8 @@
 // except according to those terms.
 
 pub mod common;
+mod dissolve;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod zone_set;
 //pub mod dggrid;
@@ -0,0 +1,131 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Serde support for the zone types, enabled with the `serde` feature. The
+// representation is kept independent of the geo crate and is stable:
+//
+// - `ZoneID` is a plain string, validated when deserialised.
+// - `Zone` is a map with the keys `id`, `region`, `center`, `vertex_count`,
+//   `children` and `neighbors`. The region is a list of rings, the exterior
+//   first, each ring a closed list of `[lon, lat]` pairs, as in the coordinates
+//   of a GeoJSON polygon. The center is a single `[lon, lat]` pair. Children and
+//   neighbours are lists of zone ids and may be omitted when empty.
+// - `Zones` is a list of zones.
+// - `ZoneSet` is its compact string form, see its `Display` implementation.
+
+use crate::models::common::{Zone, ZoneID, Zones};
+use crate::models::zone_set::ZoneSet;
+use geo::{Coord, LineString, Point, Polygon};
+use serde::de::{self, Deserializer};
+use serde::ser::{SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+
+impl Serialize for ZoneID {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.id)
+    }
+}
+
+impl<'de> Deserialize<'de> for ZoneID {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        ZoneID::new(&id).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ZoneRepr {
+    id: ZoneID,
+    region: Vec<Vec<[f64; 2]>>,
+    center: [f64; 2],
+    vertex_count: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    children: Vec<ZoneID>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    neighbors: Vec<ZoneID>,
+}
+
+impl From<&Zone> for ZoneRepr {
+    fn from(zone: &Zone) -> Self {
+        let ring = |ls: &LineString| ls.coords().map(|c| [c.x, c.y]).collect::<Vec<_>>();
+        let mut region = vec![];
+        if !zone.region.exterior().0.is_empty() {
+            region.push(ring(zone.region.exterior()));
+            region.extend(zone.region.interiors().iter().map(ring));
+        }
+        Self {
+            id: zone.id.clone(),
+            region,
+            center: [zone.center.x(), zone.center.y()],
+            vertex_count: zone.vertex_count,
+            children: zone.children.clone(),
+            neighbors: zone.neighbors.clone(),
+        }
+    }
+}
+
+impl From<ZoneRepr> for Zone {
+    fn from(repr: ZoneRepr) -> Self {
+        let mut rings = repr
+            .region
+            .into_iter()
+            .map(|ring| LineString::new(ring.into_iter().map(|[x, y]| Coord { x, y }).collect()));
+        let exterior = rings.next().unwrap_or_else(|| LineString::new(vec![]));
+        Zone {
+            id: repr.id,
+            region: Polygon::new(exterior, rings.collect()),
+            center: Point::new(repr.center[0], repr.center[1]),
+            vertex_count: repr.vertex_count,
+            children: repr.children,
+            neighbors: repr.neighbors,
+        }
+    }
+}
+
+impl Serialize for Zone {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ZoneRepr::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Zone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ZoneRepr::deserialize(deserializer).map(Zone::from)
+    }
+}
+
+impl Serialize for Zones {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for zone in self {
+            seq.serialize_element(zone)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Zones {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Zone>::deserialize(deserializer).map(Zones::from)
+    }
+}
+
+impl Serialize for ZoneSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ZoneSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,299 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::models::common::{ZoneID, ZoneQueryOptions};
+use crate::ports::dggrs::DggrsPort;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+/// A set of zone ids, possibly at different depths. A zone covers the zones
+/// descending from it, as given by `zone_ancestor`, so set operations compare
+/// zones through their ancestors without expanding them, e.g. a region given at
+/// depth 5 can be subtracted from one given at depth 7. Only a zone partly
+/// covered by the other operand is refined, into its sub-zones as returned by
+/// `zones_from_parents`. The results are compacted.
+///
+/// With aperture 3 and 7 hexagons the descendants of a zone do not cover it
+/// exactly, the results are the best approximation the DGGRS offers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZoneSet {
+    ids: BTreeSet<ZoneID>,
+}
+
+impl ZoneSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a set from zone ids carrying their depth, i.e. 18 character ids.
+    pub fn from_ids<I: IntoIterator<Item = ZoneID>>(ids: I) -> Result<Self, String> {
+        let mut set = Self::new();
+        for id in ids {
+            set.insert(id)?;
+        }
+        Ok(set)
+    }
+
+    /// Adds a zone id, which must carry its depth. Returns whether it was new.
+    pub fn insert(&mut self, id: ZoneID) -> Result<bool, String> {
+        if id.depth().is_none() {
+            return Err(format!("Zone id {} does not carry a depth", id));
+        }
+        Ok(self.ids.insert(id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ZoneID> {
+        self.ids.iter()
+    }
+
+    /// The depths of the zones in the set.
+    pub fn depths(&self) -> BTreeSet<u8> {
+        self.ids.iter().filter_map(ZoneID::depth).collect()
+    }
+
+    /// Replaces every zone coarser than `depth` by its sub-zones at `depth`, with
+    /// one query per depth of the refined zones. Zones at `depth` or finer are
+    /// kept as they are.
+    pub fn refine(&self, depth: u8, dggrs: &dyn DggrsPort) -> ZoneSet {
+        let (coarse, mut ids): (BTreeSet<ZoneID>, BTreeSet<ZoneID>) = self
+            .ids
+            .iter()
+            .cloned()
+            .partition(|id| id.depth().is_some_and(|d| d < depth));
+        if !coarse.is_empty() {
+            let coarse: Vec<ZoneID> = coarse.into_iter().collect();
+            let options = ZoneQueryOptions::centroid_only();
+            ids.extend(
+                dggrs
+                    .zones_from_parents(depth, &coarse, &options)
+                    .into_iter()
+                    .map(|zone| zone.id),
+            );
+        }
+        ZoneSet { ids }
+    }
+
+    /// Replaces every complete set of sub-zones by their parent, from the finest
+    /// depth up, with one query per depth.
+    pub fn compact(&self, dggrs: &dyn DggrsPort) -> ZoneSet {
+        let mut ids = self.ids.clone();
+        let options = ZoneQueryOptions::centroid_only();
+        let finest = self.depths().into_iter().max().unwrap_or_default();
+        for depth in (1..=finest).rev() {
+            let mut siblings: BTreeMap<ZoneID, BTreeSet<ZoneID>> = BTreeMap::new();
+            for id in ids.iter().filter(|id| id.depth() == Some(depth)) {
+                if let Some(parent) = dggrs.zone_ancestor(id, depth - 1) {
+                    siblings.entry(parent).or_default().insert(id.clone());
+                }
+            }
+            if siblings.is_empty() {
+                continue;
+            }
+
+            let parents: Vec<ZoneID> = siblings.keys().cloned().collect();
+            let mut complete: BTreeMap<ZoneID, BTreeSet<ZoneID>> = BTreeMap::new();
+            for zone in dggrs.zones_from_parents(depth, &parents, &options) {
+                if let Some(parent) = dggrs.zone_ancestor(&zone.id, depth - 1)
+                    && siblings.contains_key(&parent)
+                {
+                    complete.entry(parent).or_default().insert(zone.id);
+                }
+            }
+            for (parent, members) in siblings {
+                if complete.get(&parent) == Some(&members) {
+                    ids.retain(|id| !members.contains(id));
+                    ids.insert(parent);
+                }
+            }
+        }
+        ZoneSet { ids }
+    }
+
+    /// Zones in either set.
+    pub fn union(&self, other: &ZoneSet, dggrs: &dyn DggrsPort) -> ZoneSet {
+        let all = ZoneSet {
+            ids: self.ids.union(&other.ids).cloned().collect(),
+        };
+        let depths = all.depths();
+        ZoneSet {
+            ids: all
+                .ids
+                .iter()
+                .filter(|id| !all.covers_strictly(id, &depths, dggrs))
+                .cloned()
+                .collect(),
+        }
+        .compact(dggrs)
+    }
+
+    /// Zones in both sets.
+    pub fn intersection(&self, other: &ZoneSet, dggrs: &dyn DggrsPort) -> ZoneSet {
+        let (depths, other_depths) = (self.depths(), other.depths());
+        let ids = self
+            .ids
+            .iter()
+            .filter(|id| other.covers(id, &other_depths, dggrs))
+            .chain(
+                other
+                    .ids
+                    .iter()
+                    .filter(|id| self.covers(id, &depths, dggrs)),
+            )
+            .cloned()
+            .collect();
+        ZoneSet { ids }.compact(dggrs)
+    }
+
+    /// Zones in this set but not in `other`.
+    pub fn difference(&self, other: &ZoneSet, dggrs: &dyn DggrsPort) -> ZoneSet {
+        self.subtract(other, dggrs).compact(dggrs)
+    }
+
+    /// Whether every zone of `other` is covered by this set.
+    pub fn contains(&self, other: &ZoneSet, dggrs: &dyn DggrsPort) -> bool {
+        other.subtract(self, dggrs).is_empty()
+    }
+
+    /// Whether the zone `id` is covered by this set.
+    pub fn contains_zone(&self, id: &ZoneID, dggrs: &dyn DggrsPort) -> bool {
+        if self.covers(id, &self.depths(), dggrs) {
+            return true;
+        }
+        ZoneSet::from_ids([id.clone()]).is_ok_and(|other| self.contains(&other, dggrs))
+    }
+
+    /// Whether the zone `id` or one of its ancestors is in the set, `depths`
+    /// being the depths of the set, computed once per operation.
+    fn covers(&self, id: &ZoneID, depths: &BTreeSet<u8>, dggrs: &dyn DggrsPort) -> bool {
+        self.ids.contains(id) || self.covers_strictly(id, depths, dggrs)
+    }
+
+    /// Whether one of the ancestors of the zone `id` is in the set, see `covers`.
+    fn covers_strictly(&self, id: &ZoneID, depths: &BTreeSet<u8>, dggrs: &dyn DggrsPort) -> bool {
+        let Some(depth) = id.depth() else {
+            return false;
+        };
+        depths
+            .range(..depth)
+            .filter_map(|&d| dggrs.zone_ancestor(id, d))
+            .any(|ancestor| self.ids.contains(&ancestor))
+    }
+
+    /// The zones of this set not covered by `other`, without compaction. Zones
+    /// with descendants in `other` are refined to the depth of their finest
+    /// descendant, with one query per pair of depths.
+    fn subtract(&self, other: &ZoneSet, dggrs: &dyn DggrsPort) -> ZoneSet {
+        // The finest depth of the zones of `other` below each of their ancestors
+        // at the depths of this set
+        let (depths, other_depths) = (self.depths(), other.depths());
+        let mut finest: HashMap<ZoneID, u8> = HashMap::new();
+        for id in &other.ids {
+            let Some(depth) = id.depth() else { continue };
+            for d in depths.range(..depth) {
+                if let Some(ancestor) = dggrs.zone_ancestor(id, *d) {
+                    let entry = finest.entry(ancestor).or_default();
+                    *entry = (*entry).max(depth);
+                }
+            }
+        }
+
+        let mut ids = BTreeSet::new();
+        let mut split: BTreeMap<(u8, u8), Vec<ZoneID>> = BTreeMap::new();
+        for id in &self.ids {
+            if other.covers(id, &other_depths, dggrs) {
+                continue;
+            }
+            match (id.depth(), finest.get(id)) {
+                (Some(depth), Some(&target)) => {
+                    split.entry((depth, target)).or_default().push(id.clone())
+                }
+                _ => {
+                    ids.insert(id.clone());
+                }
+            }
+        }
+
+        let options = ZoneQueryOptions::centroid_only();
+        for ((depth, target), parents) in split {
+            let parents: BTreeSet<ZoneID> = parents.into_iter().collect();
+            let parent_ids: Vec<ZoneID> = parents.iter().cloned().collect();
+            for zone in dggrs.zones_from_parents(target, &parent_ids, &options) {
+                // Keep the descendants of the split zones only, not of their neighbours
+                let descends = dggrs
+                    .zone_ancestor(&zone.id, depth)
+                    .is_some_and(|ancestor| parents.contains(&ancestor));
+                if descends && !other.covers(&zone.id, &other_depths, dggrs) {
+                    ids.insert(zone.id);
+                }
+            }
+        }
+        ZoneSet { ids }
+    }
+}
+
+impl IntoIterator for ZoneSet {
+    type Item = ZoneID;
+    type IntoIter = std::collections::btree_set::IntoIter<ZoneID>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ids.into_iter()
+    }
+}
+
+/// The compact form lists the zones grouped by depth, the depth written once
+/// followed by the addresses without the depth prefix, e.g.
+/// `5:4710bfffffffffff,4711bfffffffffff;6:4710b7ffffffffff`.
+impl fmt::Display for ZoneSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut current: Option<&str> = None;
+        for id in &self.ids {
+            let (depth, address) = id.id.split_at(2);
+            if current == Some(depth) {
+                write!(f, ",{}", address)?;
+            } else {
+                if current.is_some() {
+                    write!(f, ";")?;
+                }
+                write!(f, "{}:{}", depth.parse::<u8>().unwrap_or_default(), address)?;
+                current = Some(depth);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ZoneSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = ZoneSet::new();
+        for group in s.split(';').filter(|g| !g.trim().is_empty()) {
+            let (depth, addresses) = group
+                .split_once(':')
+                .ok_or_else(|| format!("Missing depth in zone set group {}", group))?;
+            let depth = depth
+                .trim()
+                .parse::<u8>()
+                .map_err(|e| format!("Invalid depth {}: {}", depth, e))?;
+            for address in addresses.split(',') {
+                set.insert(ZoneID::new(&format!("{:02}{}", depth, address.trim()))?)?;
+            }
+        }
+        Ok(set)
+    }
+}
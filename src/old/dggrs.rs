@@ -6,8 +6,7 @@
 // discretion. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-mod types;
-use types::{CellId, VolumeId};
+use crate::types::{CellId, VolumeId};
 
 /// A DGGRS provides geo-location on the Earth's surface based on a Discrete
 /// Global Grid System. It translates geographic coordinates into cell
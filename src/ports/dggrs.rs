@@ -7,18 +7,139 @@
 // discretion. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use crate::models::common::Zones;
+use crate::models::common::{PointBinning, ResolutionInfo, Zone, ZoneID, ZoneQueryOptions, Zones};
 use geo::Point;
+use std::collections::BTreeMap;
 // That is the port
 pub trait DggrsPort: Send + Sync {
-    fn zones_from_bbox(&self, depth: u8, densify: bool, bbox: Option<Vec<Vec<f64>>>) -> Zones;
-    fn zone_from_point(&self, depth: u8, point: Point, densify: bool) -> Zones;
+    fn zones_from_bbox(
+        &self,
+        depth: u8,
+        options: &ZoneQueryOptions,
+        bbox: Option<Vec<Vec<f64>>>,
+    ) -> Zones;
+    /// Same as `zones_from_bbox`, but yields the zones one at a time. Backends that can parse
+    /// their output incrementally keep the memory use bounded, which makes global requests at
+    /// fine depths feasible.
+    fn zones_from_bbox_iter(
+        &self,
+        depth: u8,
+        options: &ZoneQueryOptions,
+        bbox: Option<Vec<Vec<f64>>>,
+    ) -> Box<dyn Iterator<Item = Zone> + Send> {
+        Box::new(self.zones_from_bbox(depth, options, bbox).into_iter())
+    }
+    /// The zone at `depth` containing the point. A single zone is returned, so there is no
+    /// streaming variant.
+    fn zone_from_point(&self, depth: u8, point: Point, options: &ZoneQueryOptions) -> Zones;
+    /// The id of the zone at `depth` containing each point, `None` where no zone is found.
+    /// Fails when the backend could not run the lookup at all. Backends able to look up many
+    /// points at once override this, the default queries the points one at a time.
+    fn zone_ids_from_points(
+        &self,
+        depth: u8,
+        points: &[Point],
+    ) -> Result<Vec<Option<ZoneID>>, String> {
+        let options = ZoneQueryOptions::centroid_only();
+        Ok(points
+            .iter()
+            .map(|point| {
+                let zones = self.zone_from_point(depth, *point, &options);
+                zones.iter().next().map(|zone| zone.id.clone())
+            })
+            .collect())
+    }
+    /// Bins the points, with one value each, into the zones at `depth` containing them, giving
+    /// the statistic of every zone with at least one point. The values are ignored, and may
+    /// be empty, for `Count` and `Presence`. The default looks the points up with
+    /// `zone_ids_from_points`, backends with native binning override this. The DGGRID
+    /// backends bin natively, except for `Min` and `Max`, which DGGRID does not compute and
+    /// fall back to a `TRANSFORM_POINTS` lookup. Fails when the other statistics are not
+    /// given one value per point, or the backend could not bin the points or read the result.
+    fn bin_points(
+        &self,
+        depth: u8,
+        points: &[Point],
+        values: &[f64],
+        method: PointBinning,
+    ) -> Result<BTreeMap<ZoneID, f64>, String> {
+        method.check_values(points.len(), values)?;
+        method.bin(&self.zone_ids_from_points(depth, points)?, values)
+    }
     fn zones_from_parent(
         &self,
         depth: u8,
         parent_zone_id: String,
         // clip_cell_res: u8,
-        densify: bool,
+        options: &ZoneQueryOptions,
     ) -> Zones;
-    fn zone_from_id(&self, zone_id: String, densify: bool) -> Zones;
+    /// Same as `zones_from_parent`, but yields the zones one at a time, see
+    /// `zones_from_bbox_iter`.
+    fn zones_from_parent_iter(
+        &self,
+        depth: u8,
+        parent_zone_id: String,
+        options: &ZoneQueryOptions,
+    ) -> Box<dyn Iterator<Item = Zone> + Send> {
+        Box::new(
+            self.zones_from_parent(depth, parent_zone_id, options)
+                .into_iter(),
+        )
+    }
+    /// The sub-zones at `depth` of several parent zones, each zone returned once. Backends
+    /// able to clip to several parents at once override this, the default queries the
+    /// parents one at a time.
+    fn zones_from_parents(
+        &self,
+        depth: u8,
+        parent_zone_ids: &[ZoneID],
+        options: &ZoneQueryOptions,
+    ) -> Zones {
+        let mut zones: Zones = parent_zone_ids
+            .iter()
+            .flat_map(|id| self.zones_from_parent(depth, id.id.clone(), options))
+            .collect();
+        zones.dedup();
+        zones
+    }
+    /// The zone at `depth` the zone descends from, itself at its own depth, or `None` when
+    /// `depth` is finer than the zone. Backends with a hierarchical index derive it from the
+    /// id, the default looks up the zone at `depth` containing the centroid of the zone.
+    fn zone_ancestor(&self, zone_id: &ZoneID, depth: u8) -> Option<ZoneID> {
+        match zone_id.depth()? {
+            d if d < depth => None,
+            d if d == depth => Some(zone_id.clone()),
+            _ => {
+                let options = ZoneQueryOptions::centroid_only();
+                let center = self
+                    .zone_from_id(zone_id.id.clone(), &options)
+                    .iter()
+                    .next()?
+                    .center;
+                let zones = self.zone_from_point(depth, center, &options);
+                zones.iter().next().map(|zone| zone.id.clone())
+            }
+        }
+    }
+    fn zone_from_id(&self, zone_id: String, options: &ZoneQueryOptions) -> Zones;
+    /// The zones with the given ids, each returned once. Backends able to look up many zones
+    /// at once override this, the default queries the zones one at a time.
+    fn zones_from_ids(&self, zone_ids: &[ZoneID], options: &ZoneQueryOptions) -> Zones {
+        let mut zones: Zones = zone_ids
+            .iter()
+            .flat_map(|id| self.zone_from_id(id.id.clone(), options))
+            .collect();
+        zones.dedup();
+        zones
+    }
+    /// The finest depth supported by the DGGRS.
+    fn max_depth(&self) -> u8;
+    /// Zone count, mean area, spacing and characteristic length scale at a given depth.
+    fn resolution_info(&self, depth: u8) -> ResolutionInfo;
+    /// Resolution statistics for every depth of the DGGRS, coarsest first.
+    fn resolution_table(&self) -> Vec<ResolutionInfo> {
+        (0..=self.max_depth())
+            .map(|depth| self.resolution_info(depth))
+            .collect()
+    }
 }
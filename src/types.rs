@@ -0,0 +1,327 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::models::common::{EARTH_RADIUS_KM, ZoneID, ZoneQueryOptions};
+use crate::ports::dggrs::DggrsPort;
+use geo::{Point, Polygon};
+
+// Bit layout of a cell id, similar to the H3 index but sized by the refinement level:
+// - bit 0:
+//          0 if each refinement level takes two bits (refinement ratio 3 or 4),
+//          1 if it takes three bits (refinement ratio 7). This number is referred
+//          to as n_bits_hierarchy_id below
+// - bits 1 to 3:
+//          the initial Platonic solid
+// - bits 4 to (3+n_bits_refinement_level):
+//          where n_bits_refinement_level = log_2(floor(119/n_bits_hierarchy_id)),
+//          the refinement level
+// - bits (4+n_bits_refinement_level) to (8+n_bits_refinement_level):
+//          the face of the Platonic solid
+// - remaining bits:
+//          each group of n_bits_hierarchy_id bits is an index in the hierarchy
+
+/// Identifies a cell, see the bit layout above. The variant is the smallest
+/// integer holding all the bits of the id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellId {
+    U32(u32),
+    U64(u64),
+    U128(u128),
+}
+
+/// The Platonic solid, or the truncated icosahedron, the grid starts from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnitPolyhedron {
+    Tetrahedron = 0,
+    Cube = 1,
+    Octahedron = 2,
+    Dodecahedron = 3,
+    Icosahedron = 4,
+    TruncatedIcosahedron = 5,
+}
+
+impl CellId {
+    /// The cell reached from `face_id` of the polyhedron by the indices in
+    /// `hierarchy`, one per refinement level.
+    ///
+    /// # Panics
+    ///
+    /// If the refinement ratio is not 3, 4 or 7, an index is not smaller than
+    /// the refinement ratio, or the id does not fit in 128 bits.
+    pub fn new(
+        refinement_ratio: u8,
+        initial_discrete_global_grid: UnitPolyhedron,
+        face_id: u8,
+        hierarchy: &[u8],
+    ) -> Self {
+        let n_bits_hierarchy_id: usize = match refinement_ratio {
+            3 | 4 => 2,
+            7 => 3,
+            _ => panic!("Valid options for refinement_ratio are 3, 4 or 7"),
+        };
+        let n_bits_refinement_level = (119 / n_bits_hierarchy_id).ilog2() as usize;
+        let n_bits_total = 9 + n_bits_refinement_level + hierarchy.len() * n_bits_hierarchy_id;
+        assert!(
+            n_bits_total <= 128,
+            "The refinement level can not be stored in 128 bits"
+        );
+
+        // Bit 0: refinement ratio flag, bits 1-3: polyhedron
+        let mut bits =
+            (refinement_ratio == 7) as u128 | (initial_discrete_global_grid as u128) << 1;
+        let mut offset = 4;
+
+        bits |= (hierarchy.len() as u128) << offset;
+        offset += n_bits_refinement_level;
+
+        bits |= (face_id as u128) << offset;
+        offset += 5;
+
+        for (i, &ix) in hierarchy.iter().enumerate() {
+            assert!(
+                ix < refinement_ratio,
+                "Each index in the hierarchy should be smaller than the refinement ratio"
+            );
+            bits |= (ix as u128) << (offset + i * n_bits_hierarchy_id);
+        }
+
+        if n_bits_total <= 32 {
+            CellId::U32(bits as u32)
+        } else if n_bits_total <= 64 {
+            CellId::U64(bits as u64)
+        } else {
+            CellId::U128(bits)
+        }
+    }
+
+    pub fn bits(&self) -> u128 {
+        match self {
+            CellId::U32(v) => *v as u128,
+            CellId::U64(v) => *v as u128,
+            CellId::U128(v) => *v,
+        }
+    }
+
+    pub fn bit_length(&self) -> u8 {
+        match self {
+            CellId::U32(_) => 32,
+            CellId::U64(_) => 64,
+            CellId::U128(_) => 128,
+        }
+    }
+}
+
+// For volumetric ids it is assumed a radial expansion of the associated cell
+// - bit 0:
+//         sign, 1 for layers below the sea level and 0 for those above it
+// - bits 1-7:
+//         refinement_level, expressed considering refinement_ratio 2.
+//         The thickness of the layers is determined by the refinement_level as:
+//         earth_radius / 2^refinement_level
+// - bits 8 to (7+refinement_level):
+//         index of the layer, counted from the sea level outwards, or downwards below it.
+
+/// Authalic radius of the Earth in metres, see [`EARTH_RADIUS_KM`], the thickness of the
+/// layers at refinement level 0.
+pub const EARTH_RADIUS_M: f64 = EARTH_RADIUS_KM * 1000.0;
+
+/// The finest refinement level, whose ids take all 128 bits.
+pub const MAX_ELEVATION_LEVEL: u8 = 120;
+
+/// Identifies a layer of altitudes, see the bit layout above. Altitudes are in
+/// metres above the sea level and span from minus to plus the Earth radius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElevationId {
+    U32(u32),
+    U64(u64),
+    U128(u128),
+}
+
+impl ElevationId {
+    /// The layer `index` at `refinement_level` above the sea level, or below it.
+    pub fn new(below_sea_level: bool, refinement_level: u8, index: u128) -> Result<Self, String> {
+        if refinement_level > MAX_ELEVATION_LEVEL {
+            return Err(format!(
+                "Refinement level {} exceeds the maximum {}",
+                refinement_level, MAX_ELEVATION_LEVEL
+            ));
+        }
+        if index >> refinement_level != 0 {
+            return Err(format!(
+                "Layer {} does not exist at refinement level {}",
+                index, refinement_level
+            ));
+        }
+
+        let bits = below_sea_level as u128 | (refinement_level as u128) << 1 | index << 8;
+        let n_bits_total = 8 + refinement_level as u16;
+        Ok(if n_bits_total <= 32 {
+            ElevationId::U32(bits as u32)
+        } else if n_bits_total <= 64 {
+            ElevationId::U64(bits as u64)
+        } else {
+            ElevationId::U128(bits)
+        })
+    }
+
+    /// The layer at `refinement_level` containing `altitude`, in metres. Each
+    /// layer includes its lower bound, the one closer to the Earth centre below
+    /// the sea level.
+    pub fn from_altitude(altitude: f64, refinement_level: u8) -> Result<Self, String> {
+        if !(-EARTH_RADIUS_M..EARTH_RADIUS_M).contains(&altitude) {
+            return Err(format!(
+                "Altitude {} m is not within the Earth radius",
+                altitude
+            ));
+        }
+        let thickness = Self::thickness(refinement_level);
+        let last = (1u128 << refinement_level.min(MAX_ELEVATION_LEVEL)) - 1;
+        if altitude >= 0.0 {
+            let index = (altitude / thickness).floor() as u128;
+            Self::new(false, refinement_level, index.min(last))
+        } else {
+            let index = ((-altitude / thickness).ceil() as u128).saturating_sub(1);
+            Self::new(true, refinement_level, index.min(last))
+        }
+    }
+
+    /// Thickness in metres of the layers at `refinement_level`.
+    pub fn thickness(refinement_level: u8) -> f64 {
+        EARTH_RADIUS_M / 2f64.powi(refinement_level as i32)
+    }
+
+    pub fn bits(&self) -> u128 {
+        match self {
+            ElevationId::U32(v) => *v as u128,
+            ElevationId::U64(v) => *v as u128,
+            ElevationId::U128(v) => *v,
+        }
+    }
+
+    pub fn bit_length(&self) -> u8 {
+        match self {
+            ElevationId::U32(_) => 32,
+            ElevationId::U64(_) => 64,
+            ElevationId::U128(_) => 128,
+        }
+    }
+
+    pub fn is_below_sea_level(&self) -> bool {
+        self.bits() & 1 == 1
+    }
+
+    pub fn refinement_level(&self) -> u8 {
+        (self.bits() >> 1 & 0x7f) as u8
+    }
+
+    pub fn index(&self) -> u128 {
+        self.bits() >> 8
+    }
+
+    /// The lowest and highest altitude of the layer, in metres.
+    pub fn bounds(&self) -> (f64, f64) {
+        let thickness = Self::thickness(self.refinement_level());
+        let index = self.index() as f64;
+        if self.is_below_sea_level() {
+            (-(index + 1.0) * thickness, -index * thickness)
+        } else {
+            (index * thickness, (index + 1.0) * thickness)
+        }
+    }
+
+    /// The layer at the previous refinement level containing this one, `None` at level 0.
+    pub fn parent(&self) -> Option<Self> {
+        let level = self.refinement_level().checked_sub(1)?;
+        Self::new(self.is_below_sea_level(), level, self.index() >> 1).ok()
+    }
+
+    /// The two layers at the next refinement level making up this one, the one
+    /// closer to the sea level first. `None` at the finest level.
+    pub fn children(&self) -> Option<[Self; 2]> {
+        let level = self.refinement_level() + 1;
+        let below = self.is_below_sea_level();
+        let index = self.index() << 1;
+        Some([
+            Self::new(below, level, index).ok()?,
+            Self::new(below, level, index + 1).ok()?,
+        ])
+    }
+}
+
+/// The space between two altitudes, in metres, above the region of a zone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundingVolume {
+    pub region: Polygon,
+    pub bottom: f64,
+    pub top: f64,
+}
+
+/// Identifies a volumetric cell, the radial expansion of a zone over a layer
+/// of altitudes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VolumeId {
+    pub zone: ZoneID,
+    pub elevation: ElevationId,
+}
+
+impl VolumeId {
+    pub fn new(zone: ZoneID, elevation: ElevationId) -> Self {
+        VolumeId { zone, elevation }
+    }
+
+    /// The volume containing the point, in longitude and latitude, at
+    /// `altitude` metres, with the zone at `depth` of the DGGRS and the layer at
+    /// `refinement_level`.
+    pub fn from_point(
+        dggrs: &dyn DggrsPort,
+        point: Point,
+        altitude: f64,
+        depth: u8,
+        refinement_level: u8,
+    ) -> Result<Self, String> {
+        let elevation = ElevationId::from_altitude(altitude, refinement_level)?;
+        let zones = dggrs.zone_from_point(depth, point, &ZoneQueryOptions::centroid_only());
+        let zone = zones
+            .iter()
+            .next()
+            .ok_or(format!("No zone at depth {} contains {:?}", depth, point))?;
+        Ok(Self::new(zone.id.clone(), elevation))
+    }
+
+    /// The region of the zone and the altitudes of the layer, `None` if the DGGRS
+    /// does not know the zone.
+    pub fn bounding_volume(&self, dggrs: &dyn DggrsPort) -> Option<BoundingVolume> {
+        let options = ZoneQueryOptions {
+            children: false,
+            neighbors: false,
+            ..ZoneQueryOptions::default()
+        };
+        let zones = dggrs.zone_from_id(self.zone.id.clone(), &options);
+        let (bottom, top) = self.elevation.bounds();
+        Some(BoundingVolume {
+            region: zones.iter().next()?.region.clone(),
+            bottom,
+            top,
+        })
+    }
+
+    /// The volume over the same zone with the layer at the previous refinement level.
+    pub fn vertical_parent(&self) -> Option<Self> {
+        Some(Self::new(self.zone.clone(), self.elevation.parent()?))
+    }
+
+    /// The two volumes over the same zone with the layers at the next refinement level.
+    pub fn vertical_children(&self) -> Option<[Self; 2]> {
+        let [lower, upper] = self.elevation.children()?;
+        Some([
+            Self::new(self.zone.clone(), lower),
+            Self::new(self.zone.clone(), upper),
+        ])
+    }
+}
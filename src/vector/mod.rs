@@ -0,0 +1,434 @@
+// Copyright 2025 contributors to the GeoPlegmata project.
+// Originally authored by Michael Jendryke (GeoInsight GmbH, michael.jendryke@geoinsight.ai)
+//
+// Licenced under the Apache Licence, Version 2.0 <LICENCE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENCE-MIT or http://opensource.org/licenses/MIT>, at your
+// discretion. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::models::common::{ZoneID, ZoneQueryOptions};
+use crate::ports::dggrs::DggrsPort;
+use geo::{Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+/// Identifies what a vector holds: the DGGRS and depth of the zone ids of its
+/// geometries, and the fields of its attribute table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorMetadata {
+    /// Name of the DGGRS, e.g. `IGEO7`.
+    pub dggrs: String,
+    /// Depth of the zones making up the vertices.
+    pub depth: u8,
+    /// Names of the attributes of every feature.
+    pub fields: Vec<String>,
+}
+
+/// A geometry whose vertices are zones. Rings are stored open, without
+/// repeating the first vertex at the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VectorGeometry {
+    Point(ZoneID),
+    LineString(Vec<ZoneID>),
+    /// The exterior ring followed by the holes.
+    Polygon(Vec<Vec<ZoneID>>),
+    MultiPoint(Vec<ZoneID>),
+    MultiLineString(Vec<Vec<ZoneID>>),
+    MultiPolygon(Vec<Vec<Vec<ZoneID>>>),
+}
+
+impl VectorGeometry {
+    /// The vertices of the geometry, in order.
+    pub fn zone_ids(&self) -> Vec<&ZoneID> {
+        match self {
+            VectorGeometry::Point(id) => vec![id],
+            VectorGeometry::LineString(ids) | VectorGeometry::MultiPoint(ids) => {
+                ids.iter().collect()
+            }
+            VectorGeometry::Polygon(rings) | VectorGeometry::MultiLineString(rings) => {
+                rings.iter().flatten().collect()
+            }
+            VectorGeometry::MultiPolygon(polygons) => polygons.iter().flatten().flatten().collect(),
+        }
+    }
+}
+
+/// Value of an attribute of a feature.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Bool(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        AttributeValue::Integer(value)
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        AttributeValue::Float(value)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        AttributeValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        AttributeValue::Text(value)
+    }
+}
+
+/// A geometry with its record in the attribute table, one value per field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feature {
+    pub geometry: VectorGeometry,
+    pub attributes: Vec<AttributeValue>,
+}
+
+/// A DGGS vector, a collection of features whose geometries have zones of a
+/// DGGRS at a fixed depth as vertices, with an attribute table. Geometries in
+/// longitude and latitude are snapped to the zones containing their vertices,
+/// and converted back through the zone centroids.
+#[derive(Clone)]
+pub struct Vector {
+    dggrs: Arc<dyn DggrsPort>,
+    metadata: VectorMetadata,
+    features: Vec<Feature>,
+}
+
+impl Vector {
+    /// An empty vector of the DGGRS `name`, served by `dggrs`, with vertices at
+    /// `depth` and the attribute `fields`.
+    pub fn new(
+        name: &str,
+        dggrs: Arc<dyn DggrsPort>,
+        depth: u8,
+        fields: Vec<String>,
+    ) -> Result<Self, String> {
+        if depth > dggrs.max_depth() {
+            return Err(format!(
+                "Depth {} exceeds the maximum depth {} of {}",
+                depth,
+                dggrs.max_depth(),
+                name
+            ));
+        }
+        Ok(Self {
+            dggrs,
+            metadata: VectorMetadata {
+                dggrs: name.to_string(),
+                depth,
+                fields,
+            },
+            features: vec![],
+        })
+    }
+
+    pub fn metadata(&self) -> &VectorMetadata {
+        &self.metadata
+    }
+
+    /// Identifies the DGGRS on which the vector is based.
+    pub fn dggrs(&self) -> &str {
+        &self.metadata.dggrs
+    }
+
+    /// Depth of the zones making up the vertices.
+    pub fn depth(&self) -> u8 {
+        self.metadata.depth
+    }
+
+    pub fn fields(&self) -> &[String] {
+        &self.metadata.fields
+    }
+
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    /// The value of `field` for the feature at `index`.
+    pub fn attribute(&self, index: usize, field: &str) -> Option<&AttributeValue> {
+        let column = self.metadata.fields.iter().position(|f| f == field)?;
+        self.features.get(index)?.attributes.get(column)
+    }
+
+    /// Appends a feature, returning its index. The vertices must be at the depth
+    /// of the vector and the attributes match the fields. Lines need two distinct
+    /// zones and rings three.
+    pub fn push(
+        &mut self,
+        geometry: VectorGeometry,
+        attributes: Vec<AttributeValue>,
+    ) -> Result<usize, String> {
+        if attributes.len() != self.metadata.fields.len() {
+            return Err(format!(
+                "{} attributes given for {} fields",
+                attributes.len(),
+                self.metadata.fields.len()
+            ));
+        }
+        if let Some(id) = geometry
+            .zone_ids()
+            .into_iter()
+            .find(|id| id.depth() != Some(self.metadata.depth))
+        {
+            return Err(format!(
+                "Zone {} is not at the vector depth {}",
+                id, self.metadata.depth
+            ));
+        }
+        if let Some(part) = collapsed(&geometry) {
+            return Err(format!(
+                "{} has too few distinct zones at depth {}",
+                part, self.metadata.depth
+            ));
+        }
+        self.features.push(Feature {
+            geometry,
+            attributes,
+        });
+        Ok(self.features.len() - 1)
+    }
+
+    /// Appends a geometry in longitude and latitude, snapping every vertex to the
+    /// zone containing it. Consecutive vertices in the same zone are merged, so
+    /// short segments and small rings collapse at coarse depths. Collapsed holes
+    /// and parts of multi-part geometries are dropped, a geometry collapsing as a
+    /// whole is refused.
+    pub fn push_geo(
+        &mut self,
+        geometry: &Geometry,
+        attributes: Vec<AttributeValue>,
+    ) -> Result<usize, String> {
+        let geometry = drop_collapsed(self.snap(geometry)?);
+        self.push(geometry, attributes)
+    }
+
+    /// The geometry of the feature at `index` in longitude and latitude, with
+    /// the zone centroids as vertices.
+    pub fn to_geo(&self, index: usize) -> Option<Geometry> {
+        let feature = self.features.get(index)?;
+        let centroids = self.centroids(feature.geometry.zone_ids());
+        unsnap(&feature.geometry, &centroids)
+    }
+
+    /// The geometries of all features in longitude and latitude, see
+    /// [`Vector::to_geo`].
+    pub fn geometries(&self) -> Vec<Option<Geometry>> {
+        let centroids = self.centroids(
+            self.features
+                .iter()
+                .flat_map(|feature| feature.geometry.zone_ids()),
+        );
+        self.features
+            .iter()
+            .map(|feature| unsnap(&feature.geometry, &centroids))
+            .collect()
+    }
+
+    /// The centroid of every distinct zone, looked up in a single query.
+    fn centroids<'a, I>(&self, ids: I) -> HashMap<ZoneID, Coord>
+    where
+        I: IntoIterator<Item = &'a ZoneID>,
+    {
+        let ids: Vec<ZoneID> = ids
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .cloned()
+            .collect();
+        self.dggrs
+            .zones_from_ids(&ids, &ZoneQueryOptions::centroid_only())
+            .into_iter()
+            .map(|zone| (zone.id, zone.center.0))
+            .collect()
+    }
+
+    /// Looks up the zones of all vertices at once, then rebuilds the structure.
+    fn snap(&self, geometry: &Geometry) -> Result<VectorGeometry, String> {
+        let geometry = match geometry {
+            Geometry::Line(line) => Geometry::LineString(LineString::from(*line)),
+            Geometry::Rect(rect) => Geometry::Polygon(rect.to_polygon()),
+            Geometry::Triangle(triangle) => Geometry::Polygon(triangle.to_polygon()),
+            other => other.clone(),
+        };
+        let points: Vec<Point> = match &geometry {
+            Geometry::Point(point) => vec![*point],
+            Geometry::MultiPoint(points) => points.0.clone(),
+            Geometry::LineString(line) => line.points().collect(),
+            Geometry::MultiLineString(lines) => lines.iter().flat_map(|l| l.points()).collect(),
+            Geometry::Polygon(polygon) => rings(polygon).flat_map(|r| r.points()).collect(),
+            Geometry::MultiPolygon(polygons) => polygons
+                .iter()
+                .flat_map(rings)
+                .flat_map(|r| r.points())
+                .collect(),
+            _ => return Err("Geometry collections are not supported".to_string()),
+        };
+        let ids = self
+            .dggrs
+            .zone_ids_from_points(self.metadata.depth, &points)?;
+        let mut ids = ids.into_iter();
+        let mut take = |n: usize, closed: bool| -> Result<Vec<ZoneID>, String> {
+            let mut vertices: Vec<ZoneID> = vec![];
+            for id in ids.by_ref().take(n) {
+                let id = id.ok_or("No zone found for a vertex")?;
+                if vertices.last() != Some(&id) {
+                    vertices.push(id);
+                }
+            }
+            if closed && vertices.len() > 1 && vertices.first() == vertices.last() {
+                vertices.pop();
+            }
+            Ok(vertices)
+        };
+        let mut take_polygon = |polygon: &Polygon| -> Result<Vec<Vec<ZoneID>>, String> {
+            rings(polygon).map(|r| take(r.0.len(), true)).collect()
+        };
+
+        Ok(match &geometry {
+            Geometry::Point(_) => VectorGeometry::Point(take(1, false)?.remove(0)),
+            Geometry::MultiPoint(points) => VectorGeometry::MultiPoint(
+                (0..points.0.len())
+                    .map(|_| take(1, false).map(|mut id| id.remove(0)))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Geometry::LineString(line) => VectorGeometry::LineString(take(line.0.len(), false)?),
+            Geometry::MultiLineString(lines) => VectorGeometry::MultiLineString(
+                lines
+                    .iter()
+                    .map(|line| take(line.0.len(), false))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Geometry::Polygon(polygon) => VectorGeometry::Polygon(take_polygon(polygon)?),
+            Geometry::MultiPolygon(polygons) => VectorGeometry::MultiPolygon(
+                polygons
+                    .iter()
+                    .map(&mut take_polygon)
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => unreachable!(),
+        })
+    }
+}
+
+impl fmt::Debug for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Vector")
+            .field("metadata", &self.metadata)
+            .field("features", &self.features.len())
+            .finish()
+    }
+}
+
+/// Lines need two distinct zones, rings three.
+fn is_line(ids: &[ZoneID]) -> bool {
+    ids.iter().collect::<HashSet<_>>().len() >= 2
+}
+
+fn is_ring(ids: &[ZoneID]) -> bool {
+    ids.iter().collect::<HashSet<_>>().len() >= 3
+}
+
+/// The kind of the first part of the geometry with too few distinct zones.
+fn collapsed(geometry: &VectorGeometry) -> Option<&'static str> {
+    let polygon = |rings: &Vec<Vec<ZoneID>>| !rings.is_empty() && rings.iter().all(|r| is_ring(r));
+    match geometry {
+        VectorGeometry::Point(_) | VectorGeometry::MultiPoint(_) => None,
+        VectorGeometry::LineString(ids) => (!is_line(ids)).then_some("The line"),
+        VectorGeometry::MultiLineString(lines) => {
+            (lines.is_empty() || !lines.iter().all(|l| is_line(l))).then_some("A line")
+        }
+        VectorGeometry::Polygon(rings) => (!polygon(rings)).then_some("A ring"),
+        VectorGeometry::MultiPolygon(polygons) => {
+            (polygons.is_empty() || !polygons.iter().all(polygon)).then_some("A ring")
+        }
+    }
+}
+
+/// Drops the collapsed holes, and the collapsed parts of multi-part geometries.
+fn drop_collapsed(geometry: VectorGeometry) -> VectorGeometry {
+    let polygon = |mut rings: Vec<Vec<ZoneID>>| {
+        if rings.len() > 1 {
+            let holes = rings.split_off(1);
+            rings.extend(holes.into_iter().filter(|hole| is_ring(hole)));
+        }
+        rings
+    };
+    match geometry {
+        VectorGeometry::Polygon(rings) => VectorGeometry::Polygon(polygon(rings)),
+        VectorGeometry::MultiLineString(lines) => {
+            VectorGeometry::MultiLineString(lines.into_iter().filter(|l| is_line(l)).collect())
+        }
+        VectorGeometry::MultiPolygon(polygons) => VectorGeometry::MultiPolygon(
+            polygons
+                .into_iter()
+                .map(polygon)
+                .filter(|rings| rings.first().is_some_and(|r| is_ring(r)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// The exterior ring of a polygon followed by its holes.
+fn rings(polygon: &Polygon) -> impl Iterator<Item = &LineString> {
+    std::iter::once(polygon.exterior()).chain(polygon.interiors())
+}
+
+/// Rebuilds a geometry from the centroids of its zones, `None` if one is missing.
+fn unsnap(geometry: &VectorGeometry, centroids: &HashMap<ZoneID, Coord>) -> Option<Geometry> {
+    let line = |ids: &[ZoneID]| -> Option<LineString> {
+        ids.iter()
+            .map(|id| centroids.get(id).copied())
+            .collect::<Option<Vec<_>>>()
+            .map(LineString::new)
+    };
+    let polygon = |rings: &[Vec<ZoneID>]| -> Option<Polygon> {
+        let mut rings = rings.iter().map(|ring| line(ring));
+        let exterior = rings.next()??;
+        Some(Polygon::new(exterior, rings.collect::<Option<_>>()?))
+    };
+    Some(match geometry {
+        VectorGeometry::Point(id) => Geometry::Point(Point(*centroids.get(id)?)),
+        VectorGeometry::MultiPoint(ids) => {
+            Geometry::MultiPoint(MultiPoint::new(line(ids)?.points().collect()))
+        }
+        VectorGeometry::LineString(ids) => Geometry::LineString(line(ids)?),
+        VectorGeometry::MultiLineString(lines) => Geometry::MultiLineString(MultiLineString::new(
+            lines.iter().map(|ids| line(ids)).collect::<Option<_>>()?,
+        )),
+        VectorGeometry::Polygon(rings) => Geometry::Polygon(polygon(rings)?),
+        VectorGeometry::MultiPolygon(polygons) => Geometry::MultiPolygon(MultiPolygon::new(
+            polygons
+                .iter()
+                .map(|rings| polygon(rings))
+                .collect::<Option<_>>()?,
+        )),
+    })
+}
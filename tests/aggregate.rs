@@ -0,0 +1,93 @@
+mod common;
+
+use common::{QuadGrid, ShiftedGrid};
+use geo_plegmata::coverage::Coverage;
+use geo_plegmata::coverage::aggregate::Aggregation;
+use std::sync::Arc;
+
+fn coverage() -> Coverage<f64> {
+    let mut coverage = Coverage::new("QUAD", Arc::new(QuadGrid), 3, 1).unwrap();
+    coverage.add_block(&QuadGrid::id(1, 1, 0)).unwrap();
+    for (x, y, value) in [
+        (4, 0, 1.0),
+        (5, 0, 2.0),
+        (4, 1, 3.0),
+        (5, 1, 2.0),
+        (6, 0, 7.0),
+    ] {
+        coverage.set(&QuadGrid::id(3, x, y), value).unwrap();
+    }
+    coverage
+}
+
+#[test]
+fn test_aggregation_methods() {
+    let coverage = coverage();
+    let zone = QuadGrid::id(2, 2, 0);
+    let value = |method| *coverage.aggregate(2, method).unwrap().get(&zone).unwrap();
+
+    assert!((value(Aggregation::Sum) - 8.0).abs() < 1e-9);
+    assert!((value(Aggregation::Mean) - 2.0).abs() < 1e-9);
+    assert_eq!(value(Aggregation::Min), 1.0);
+    assert_eq!(value(Aggregation::Max), 3.0);
+    assert!((value(Aggregation::Count) - 4.0).abs() < 1e-9);
+    assert_eq!(value(Aggregation::Majority), 2.0);
+    // Cells closer to the pole are smaller
+    let weighted = value(Aggregation::AreaWeightedMean);
+    assert!(weighted > 2.0 && weighted < 3.0);
+
+    let sums = coverage.aggregate(2, Aggregation::Sum).unwrap();
+    assert_eq!(sums.get(&QuadGrid::id(2, 3, 0)), Some(&7.0));
+    assert_eq!(sums.get(&QuadGrid::id(2, 3, 1)), None);
+}
+
+#[test]
+fn test_aggregation_above_block_depth() {
+    let coverage = coverage();
+    let sums = coverage.aggregate(1, Aggregation::Sum).unwrap();
+
+    assert_eq!(sums.zone_resolution(), 0);
+    assert!((sums.get(&QuadGrid::id(1, 1, 0)).unwrap() - 15.0).abs() < 1e-9);
+    assert!(coverage.aggregate(3, Aggregation::Sum).is_err());
+}
+
+#[test]
+fn test_aggregation_of_straddling_cells() {
+    let mut coverage = Coverage::new("SHIFTED", Arc::new(ShiftedGrid), 2, 0).unwrap();
+    coverage.add_block(&QuadGrid::id(0, 0, 0)).unwrap();
+    for y in 0..2 {
+        coverage.set(&QuadGrid::id(2, 1, y), 2.0).unwrap();
+        // Half of these cells lies in either zone at depth 1
+        coverage.set(&QuadGrid::id(2, 2, y), 8.0).unwrap();
+    }
+    let zone = QuadGrid::id(1, 0, 0);
+    let value = |method| *coverage.aggregate(1, method).unwrap().get(&zone).unwrap();
+
+    assert!((value(Aggregation::Sum) - 12.0).abs() < 1e-9);
+    assert!((value(Aggregation::Mean) - 4.0).abs() < 1e-9);
+    assert!((value(Aggregation::AreaWeightedMean) - 4.0).abs() < 1e-9);
+    assert!((value(Aggregation::Count) - 3.0).abs() < 1e-9);
+    // Min and Max take the straddling cells in full
+    assert_eq!(value(Aggregation::Min), 2.0);
+    assert_eq!(value(Aggregation::Max), 8.0);
+
+    let sums = coverage.aggregate(1, Aggregation::Sum).unwrap();
+    assert!((sums.get(&QuadGrid::id(1, 1, 0)).unwrap() - 8.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_aggregation_across_the_antimeridian_conserves_sums() {
+    let mut coverage = Coverage::new("SHIFTED", Arc::new(ShiftedGrid), 2, 0).unwrap();
+    coverage.add_block(&QuadGrid::id(0, 0, 0)).unwrap();
+    for y in 0..4 {
+        for x in 0..4 {
+            coverage.set(&QuadGrid::id(2, x, y), 1.0).unwrap();
+        }
+    }
+    // The zones (1, 1, y) span 45 to 225 degrees, covering the cells x 0 west of
+    // the antimeridian in full
+    let sums = coverage.aggregate(1, Aggregation::Sum).unwrap();
+    let total: f64 = sums.iter().map(|(_, value)| value).sum();
+    assert!((total - 16.0).abs() < 1e-9, "total {}", total);
+    assert!((sums.get(&QuadGrid::id(1, 1, 0)).unwrap() - 4.0).abs() < 1e-9);
+}
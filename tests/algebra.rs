@@ -0,0 +1,62 @@
+mod common;
+
+use common::QuadGrid;
+use geo_plegmata::coverage::Coverage;
+use std::sync::Arc;
+
+fn coverage(depth: u8, values: &[(u32, u32, f64)]) -> Coverage<f64> {
+    let mut coverage = Coverage::new("QUAD", Arc::new(QuadGrid), depth, 1).unwrap();
+    coverage.add_block(&QuadGrid::id(1, 1, 0)).unwrap();
+    for &(x, y, value) in values {
+        coverage.set(&QuadGrid::id(depth, x, y), value).unwrap();
+    }
+    coverage
+}
+
+#[test]
+fn test_arithmetic_and_nodata() {
+    let a = coverage(2, &[(2, 0, 6.0), (3, 0, 1.0), (2, 1, 4.0)]);
+    let b = coverage(2, &[(2, 0, 2.0), (3, 0, 0.0)]);
+
+    let sum = a.add(&b).unwrap();
+    assert_eq!(sum.get(&QuadGrid::id(2, 2, 0)), Some(&8.0));
+    // No value in b
+    assert_eq!(sum.get(&QuadGrid::id(2, 2, 1)), None);
+
+    let ratio = a.div(&b).unwrap();
+    assert_eq!(ratio.get(&QuadGrid::id(2, 2, 0)), Some(&3.0));
+    assert_eq!(ratio.get(&QuadGrid::id(2, 3, 0)), None);
+
+    let scaled = a.map(|v| Some(v * 10.0));
+    assert_eq!(scaled.get(&QuadGrid::id(2, 2, 1)), Some(&40.0));
+}
+
+#[test]
+fn test_comparison_and_select() {
+    let a = coverage(2, &[(2, 0, 6.0), (3, 0, 1.0)]);
+    let b = coverage(2, &[(2, 0, 2.0), (3, 0, 5.0)]);
+
+    let larger = a.gt(&b).unwrap();
+    assert_eq!(larger.get(&QuadGrid::id(2, 2, 0)), Some(&true));
+    let max = larger.select(&a, &b).unwrap();
+    assert_eq!(max.get(&QuadGrid::id(2, 2, 0)), Some(&6.0));
+    assert_eq!(max.get(&QuadGrid::id(2, 3, 0)), Some(&5.0));
+}
+
+#[test]
+fn test_alignment_across_depths() {
+    let coarse = coverage(2, &[(2, 0, 10.0)]);
+    let fine = coverage(3, &[(4, 0, 1.0), (5, 0, 2.0)]);
+
+    // The coarse value is copied down to the fine cells
+    let sum = fine.add(&coarse).unwrap();
+    assert_eq!(sum.resolution(), 3);
+    assert!((sum.get(&QuadGrid::id(3, 5, 0)).unwrap() - 12.0).abs() < 1e-9);
+
+    // The fine values are averaged up to the coarse cell
+    let sum = coarse.add(&fine).unwrap();
+    assert!((sum.get(&QuadGrid::id(2, 2, 0)).unwrap() - 11.5).abs() < 1e-9);
+
+    let other = Coverage::<f64>::new("IGEO7", Arc::new(QuadGrid), 2, 1).unwrap();
+    assert!(coarse.add(&other).is_err());
+}
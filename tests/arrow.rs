@@ -0,0 +1,89 @@
+#![cfg(feature = "geoparquet")]
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float64Type, UInt8Type};
+use geo::{LineString, Point, Polygon};
+use geo_plegmata::encoding::{arrow, geoparquet};
+use geo_plegmata::models::common::{Zone, ZoneID};
+use std::fs::File;
+
+fn zone(id: &str, x: f64) -> Zone {
+    Zone {
+        id: ZoneID::new(id).unwrap(),
+        region: Polygon::new(
+            LineString::from(vec![(x, 0.0), (x + 2.0, 0.0), (x + 2.0, 1.0), (x, 0.0)]),
+            vec![],
+        ),
+        center: Point::new(x + 1.0, 0.5),
+        vertex_count: 3,
+        children: vec![ZoneID::new("0500110000000000ff").unwrap()],
+        neighbors: vec![],
+    }
+}
+
+#[test]
+fn test_record_batch_columns() {
+    let zones = vec![
+        zone("0400100000000000ff", 0.0),
+        zone("0400200000000000ff", 179.0),
+    ];
+    let batch = arrow::to_record_batch(&zones).unwrap();
+
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(
+        batch.column(0).as_string::<i32>().value(1),
+        "0400200000000000ff"
+    );
+    assert_eq!(batch.column(1).as_primitive::<UInt8Type>().value(0), 4);
+    assert_eq!(
+        batch.column(2).as_primitive::<Float64Type>().value(1),
+        180.0
+    );
+
+    let geometry = batch.column(5).as_binary::<i32>();
+    // little endian polygon and multi-polygon split at the antimeridian
+    assert_eq!(&geometry.value(0)[..5], &[1, 3, 0, 0, 0]);
+    assert_eq!(&geometry.value(1)[..5], &[1, 6, 0, 0, 0]);
+
+    let children = batch.column(6).as_list::<i32>();
+    assert_eq!(children.value(0).len(), 1);
+    assert_eq!(batch.column(7).as_list::<i32>().value(0).len(), 0);
+}
+
+#[test]
+fn test_batches_are_bounded() {
+    let zones = (0..5).map(|i| zone(&format!("04001000000000000{}", i), i as f64));
+    let sizes: Vec<usize> = arrow::to_record_batches(zones, 2)
+        .map(|batch| batch.unwrap().num_rows())
+        .collect();
+
+    assert_eq!(sizes, vec![2, 2, 1]);
+}
+
+#[test]
+fn test_geoparquet_metadata() {
+    let path = std::env::temp_dir().join("gp_zones_test.parquet");
+    let zones = vec![
+        zone("0400100000000000ff", 0.0),
+        zone("0400200000000000ff", 10.0),
+    ];
+    geoparquet::write(zones, File::create(&path).unwrap(), 1).unwrap();
+
+    let data = std::fs::read(&path).unwrap();
+    assert_eq!(&data[..4], b"PAR1");
+    assert_eq!(&data[data.len() - 4..], b"PAR1");
+
+    let geo = serde_json::json!({
+        "version": "1.1.0",
+        "primary_column": "geometry",
+        "columns": { "geometry": {
+            "encoding": "WKB",
+            "geometry_types": ["Polygon"],
+            "edges": "planar",
+            "bbox": [0.0, 0.0, 12.0, 1.0],
+        }},
+    })
+    .to_string();
+    assert!(data.windows(geo.len()).any(|w| w == geo.as_bytes()));
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::QuadGrid;
+use geo::Point;
+use geo_plegmata::adapters::dggrid::common::parse_bins;
+use geo_plegmata::adapters::dggrid::igeo7::Igeo7Impl;
+use geo_plegmata::coverage::Coverage;
+use geo_plegmata::models::common::{PointBinning, ZoneID};
+use geo_plegmata::ports::dggrs::DggrsPort;
+use std::sync::Arc;
+
+/// Three points in the depth 2 quad zone at x 2, y 2 and one in that at x 0, y 1.
+fn points() -> (Vec<Point>, Vec<f64>) {
+    let points = vec![
+        Point::new(10.0, 10.0),
+        Point::new(80.0, 40.0),
+        Point::new(45.5, 20.0),
+        Point::new(-170.0, -10.0),
+    ];
+    (points, vec![1.0, 2.0, 6.0, 4.0])
+}
+
+fn bin(method: PointBinning) -> Coverage<f64> {
+    let (points, values) = points();
+    Coverage::from_points("QUAD", Arc::new(QuadGrid), 2, 1, &points, &values, method).unwrap()
+}
+
+#[test]
+fn test_bin_points() {
+    let (full, single) = (QuadGrid::id(2, 2, 2), QuadGrid::id(2, 0, 1));
+    let expected = [
+        (PointBinning::Count, 3.0, 1.0),
+        (PointBinning::Sum, 9.0, 4.0),
+        (PointBinning::Mean, 3.0, 4.0),
+        (PointBinning::Min, 1.0, 4.0),
+        (PointBinning::Max, 6.0, 4.0),
+        (PointBinning::Presence, 1.0, 1.0),
+    ];
+    for (method, a, b) in expected {
+        let coverage = bin(method);
+        assert_eq!(coverage.get(&full), Some(&a), "{:?}", method);
+        assert_eq!(coverage.get(&single), Some(&b), "{:?}", method);
+        assert_eq!(coverage.iter().count(), 2);
+    }
+
+    // The blocks containing the points, with the empty zones left without value
+    let coverage = bin(PointBinning::Count);
+    assert_eq!(coverage.zone_ids().len(), 2);
+    assert_eq!(coverage.len(), 8);
+    assert_eq!(coverage.get(&QuadGrid::id(2, 3, 3)), None);
+}
+
+#[test]
+fn test_bin_points_without_values() {
+    let (points, _) = points();
+    let dggrs = Arc::new(QuadGrid);
+    let count = Coverage::from_points(
+        "QUAD",
+        dggrs.clone(),
+        2,
+        1,
+        &points,
+        &[],
+        PointBinning::Count,
+    );
+    assert_eq!(count.unwrap().get(&QuadGrid::id(2, 2, 2)), Some(&3.0));
+    assert!(Coverage::from_points("QUAD", dggrs, 2, 1, &points, &[], PointBinning::Mean).is_err());
+}
+
+/// Output of `BIN_POINT_VALS` with `output_count TRUE`: address, count, mean.
+const BIN_POINT_VALS: &str = "\
+4710bfffffffffff 3 2.5
+4711bfffffffffff 1 4
+";
+
+#[test]
+fn test_parse_dggrid_bins() {
+    let (a, b) = (
+        ZoneID::new("054710bfffffffffff").unwrap(),
+        ZoneID::new("054711bfffffffffff").unwrap(),
+    );
+    let parse = |method| parse_bins(BIN_POINT_VALS.as_bytes(), 5, method).unwrap();
+
+    assert_eq!(parse(PointBinning::Count)[&a], 3.0);
+    assert_eq!(parse(PointBinning::Sum)[&a], 7.5);
+    assert_eq!(parse(PointBinning::Mean)[&a], 2.5);
+    assert_eq!(parse(PointBinning::Mean)[&b], 4.0);
+
+    // The mean before the count is not a valid count
+    let swapped = "4710bfffffffffff 2.5 3\n";
+    assert!(parse_bins(swapped.as_bytes(), 5, PointBinning::Mean).is_err());
+    assert!(parse_bins("4710bfffffffffff 3\n".as_bytes(), 5, PointBinning::Sum).is_err());
+
+    let presence = "4710bfffffffffff 1\n4711bfffffffffff 0\n";
+    let bins = parse_bins(presence.as_bytes(), 5, PointBinning::Presence).unwrap();
+    assert_eq!(bins.into_iter().collect::<Vec<_>>(), vec![(a, 1.0)]);
+}
+
+#[test]
+fn test_bin_points_with_missing_values() {
+    let (points, values) = points();
+    let ids = vec![Some(QuadGrid::id(2, 2, 2)); points.len()];
+    assert!(PointBinning::Sum.bin(&ids, &values[..3]).is_err());
+    assert_eq!(PointBinning::Count.bin(&ids, &[]).unwrap().len(), 1);
+
+    assert!(
+        QuadGrid
+            .bin_points(2, &points, &values[..3], PointBinning::Mean)
+            .is_err()
+    );
+    let igeo7 = Igeo7Impl::new("/nonexistent/dggrid".into(), std::env::temp_dir());
+    for method in [PointBinning::Mean, PointBinning::Max] {
+        let error = igeo7
+            .bin_points(5, &points, &values[..3], method)
+            .unwrap_err();
+        assert_eq!(error, "3 values given for 4 points");
+    }
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::{QuadGrid, ShiftedGrid};
+use geo::{LineString, MultiPolygon, Rect, coord};
+use geo_plegmata::coverage::Coverage;
+use geo_plegmata::coverage::burn::{Burn, BurnRule, Conflict};
+use std::sync::Arc;
+
+/// Two overlapping rectangles in the depth 2 quad zones at x 2 and 3, y 2,
+/// between lon 0 and 180 and lat 0 and 45.
+fn features() -> Vec<Burn> {
+    let rect = |min_x, max_x| Rect::new(coord! { x: min_x, y: 1.0 }, coord! { x: max_x, y: 44.0 });
+    vec![
+        Burn::new(rect(1.0, 100.0), 1.0),
+        Burn::new(rect(50.0, 170.0), 5.0).with_priority(1),
+    ]
+}
+
+fn burn(features: &[Burn], rule: BurnRule, conflict: Conflict) -> Coverage<f64> {
+    Coverage::from_features("QUAD", Arc::new(QuadGrid), 2, 1, features, rule, conflict).unwrap()
+}
+
+#[test]
+fn test_burn_rules() {
+    let (west, east) = (QuadGrid::id(2, 2, 2), QuadGrid::id(2, 3, 2));
+
+    let centroid = burn(&features(), BurnRule::Centroid, Conflict::Priority);
+    assert_eq!(centroid.get(&west), Some(&1.0));
+    assert_eq!(centroid.get(&east), Some(&5.0));
+    assert_eq!(centroid.iter().count(), 2);
+
+    let intersects = burn(&features(), BurnRule::Intersects, Conflict::Priority);
+    assert_eq!(intersects.get(&west), Some(&5.0));
+    assert_eq!(intersects.get(&east), Some(&5.0));
+
+    // The first rectangle covers most of the west zone, the second most of the east one
+    let majority = burn(&features(), BurnRule::MajorityArea, Conflict::Sum);
+    assert_eq!(majority.get(&west), Some(&1.0));
+    assert_eq!(majority.get(&east), Some(&5.0));
+}
+
+#[test]
+fn test_burn_conflicts() {
+    let west = QuadGrid::id(2, 2, 2);
+    let expected = [
+        (Conflict::Sum, 6.0),
+        (Conflict::Mean, 3.0),
+        (Conflict::Min, 1.0),
+        (Conflict::Max, 5.0),
+        (Conflict::Count, 2.0),
+    ];
+    for (conflict, value) in expected {
+        let coverage = burn(&features(), BurnRule::Intersects, conflict);
+        assert_eq!(coverage.get(&west), Some(&value), "{:?}", conflict);
+    }
+
+    // Without priorities the first feature wins
+    let features: Vec<Burn> = features().into_iter().map(|f| f.with_priority(0)).collect();
+    let coverage = burn(&features, BurnRule::Intersects, Conflict::Priority);
+    assert_eq!(coverage.get(&west), Some(&1.0));
+}
+
+#[test]
+fn test_burn_lines() {
+    let road = LineString::from(vec![(-100.0, -30.0), (-10.0, -30.0)]);
+    let coverage = burn(&[Burn::new(road, 1.0)], BurnRule::Centroid, Conflict::Count);
+    assert_eq!(coverage.get(&QuadGrid::id(2, 0, 1)), Some(&1.0));
+    assert_eq!(coverage.get(&QuadGrid::id(2, 1, 1)), Some(&1.0));
+    assert_eq!(coverage.iter().count(), 2);
+}
+
+#[test]
+fn test_majority_area_across_the_antimeridian() {
+    // Zone (1, 1, 0) spans longitudes 45 to 225, the feature covers 105° of it
+    let rect = |min_x, max_x| Rect::new(coord! { x: min_x, y: -90.0 }, coord! { x: max_x, y: 0.0 });
+    let feature = MultiPolygon(vec![
+        rect(-180.0, -135.0).to_polygon(),
+        rect(120.0, 180.0).to_polygon(),
+    ]);
+    let mut coverage = Coverage::new("SHIFTED", Arc::new(ShiftedGrid), 1, 0).unwrap();
+    coverage.add_block(&QuadGrid::id(0, 0, 0)).unwrap();
+    coverage
+        .burn(
+            &[Burn::new(feature, 3.0)],
+            BurnRule::MajorityArea,
+            Conflict::Priority,
+        )
+        .unwrap();
+
+    assert_eq!(coverage.get(&QuadGrid::id(1, 1, 0)), Some(&3.0));
+    assert_eq!(coverage.get(&QuadGrid::id(1, 0, 0)), None);
+}
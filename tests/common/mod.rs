@@ -0,0 +1,246 @@
+#![allow(dead_code)]
+
+use geo::{Intersects, LineString, Point, Polygon, Rect};
+use geo_plegmata::models::common::{ResolutionInfo, Zone, ZoneID, ZoneQueryOptions, Zones};
+use geo_plegmata::ports::dggrs::DggrsPort;
+
+/// A quadtree on the plate carrée, splitting the globe into 2^d by 2^d
+/// rectangles at depth d, to test the models without DGGRID.
+#[derive(Default)]
+pub struct QuadGrid;
+
+impl QuadGrid {
+    pub fn id(depth: u8, x: u32, y: u32) -> ZoneID {
+        ZoneID::new(&format!("{:02}{:08x}{:08x}", depth, x, y)).unwrap()
+    }
+
+    fn cell(id: &str) -> (u8, u32, u32) {
+        (
+            id[..2].parse().unwrap(),
+            u32::from_str_radix(&id[2..10], 16).unwrap(),
+            u32::from_str_radix(&id[10..], 16).unwrap(),
+        )
+    }
+
+    fn rect(depth: u8, x: u32, y: u32) -> Rect {
+        let (w, h) = (
+            360.0 / (1u64 << depth) as f64,
+            180.0 / (1u64 << depth) as f64,
+        );
+        let (x0, y0) = (-180.0 + x as f64 * w, -90.0 + y as f64 * h);
+        Rect::new((x0, y0), (x0 + w, y0 + h))
+    }
+
+    fn zone(depth: u8, x: u32, y: u32, options: &ZoneQueryOptions) -> Zone {
+        let rect = Self::rect(depth, x, y);
+        let n = 1i64 << depth;
+        let (children, neighbors) = if options.centroid_only {
+            (vec![], vec![])
+        } else {
+            let children = (0..4)
+                .map(|i| Self::id(depth + 1, 2 * x + i % 2, 2 * y + i / 2))
+                .collect();
+            let mut neighbors = vec![];
+            for dx in -1..=1i64 {
+                for dy in -1..=1i64 {
+                    let (nx, ny) = ((x as i64 + dx).rem_euclid(n), y as i64 + dy);
+                    if (dx, dy) != (0, 0) && (0..n).contains(&ny) {
+                        neighbors.push(Self::id(depth, nx as u32, ny as u32));
+                    }
+                }
+            }
+            (children, neighbors)
+        };
+        Zone {
+            id: Self::id(depth, x, y),
+            region: if options.centroid_only {
+                Polygon::new(LineString::new(vec![]), vec![])
+            } else {
+                rect.to_polygon()
+            },
+            center: rect.center().into(),
+            vertex_count: 4,
+            children,
+            neighbors,
+        }
+    }
+}
+
+impl DggrsPort for QuadGrid {
+    fn zones_from_bbox(
+        &self,
+        depth: u8,
+        options: &ZoneQueryOptions,
+        bbox: Option<Vec<Vec<f64>>>,
+    ) -> Zones {
+        let n = 1u32 << depth;
+        let bbox = bbox.map(|b| Rect::new((b[0][0], b[0][1]), (b[1][0], b[1][1])));
+        let mut zones = Zones::new();
+        for y in 0..n {
+            for x in 0..n {
+                if bbox.is_none_or(|b| b.intersects(&Self::rect(depth, x, y))) {
+                    zones.push(Self::zone(depth, x, y, options));
+                }
+            }
+        }
+        zones
+    }
+
+    fn zone_from_point(&self, depth: u8, point: Point, options: &ZoneQueryOptions) -> Zones {
+        let n = (1u64 << depth) as f64;
+        let x = (((point.x() + 180.0) / 360.0 * n) as u32).min(n as u32 - 1);
+        let y = (((point.y() + 90.0) / 180.0 * n) as u32).min(n as u32 - 1);
+        vec![Self::zone(depth, x, y, options)].into()
+    }
+
+    fn zones_from_parent(
+        &self,
+        depth: u8,
+        parent_zone_id: String,
+        options: &ZoneQueryOptions,
+    ) -> Zones {
+        let (d, x, y) = Self::cell(&parent_zone_id);
+        let k = 1u32 << (depth - d);
+        let mut zones = Zones::new();
+        for cy in 0..k {
+            for cx in 0..k {
+                zones.push(Self::zone(depth, x * k + cx, y * k + cy, options));
+            }
+        }
+        zones
+    }
+
+    fn zone_from_id(&self, zone_id: String, options: &ZoneQueryOptions) -> Zones {
+        let (d, x, y) = Self::cell(&zone_id);
+        vec![Self::zone(d, x, y, options)].into()
+    }
+
+    fn max_depth(&self) -> u8 {
+        20
+    }
+
+    fn resolution_info(&self, depth: u8) -> ResolutionInfo {
+        let zone_count = 1u64 << (2 * depth as u64);
+        let mean_area = 510_065_621.7 / zone_count as f64;
+        ResolutionInfo {
+            depth,
+            zone_count,
+            mean_area,
+            spacing: mean_area.sqrt(),
+            cls: mean_area.sqrt(),
+        }
+    }
+}
+
+/// A quadtree whose odd depths are shifted east by a quarter of their zone
+/// width, so zones at those depths do not nest, as aperture 3 and 7 hexagons.
+/// The easternmost zones at an odd depth cross the antimeridian, with longitudes
+/// beyond 180 as DGGRID writes them.
+#[derive(Default)]
+pub struct ShiftedGrid;
+
+impl ShiftedGrid {
+    fn shift(depth: u8) -> f64 {
+        if depth % 2 == 1 {
+            360.0 / (1u64 << depth) as f64 / 4.0
+        } else {
+            0.0
+        }
+    }
+
+    fn rect(depth: u8, x: u32, y: u32) -> Rect {
+        let quad = QuadGrid::rect(depth, x, y);
+        let shift = Self::shift(depth);
+        Rect::new(
+            (quad.min().x + shift, quad.min().y),
+            (quad.max().x + shift, quad.max().y),
+        )
+    }
+
+    fn zone(depth: u8, x: u32, y: u32, options: &ZoneQueryOptions) -> Zone {
+        let mut zone = QuadGrid::zone(depth, x, y, options);
+        let rect = Self::rect(depth, x, y);
+        zone.center = rect.center().into();
+        if !options.centroid_only {
+            zone.region = rect.to_polygon();
+            zone.children = vec![];
+        }
+        zone
+    }
+}
+
+impl DggrsPort for ShiftedGrid {
+    fn zones_from_bbox(
+        &self,
+        depth: u8,
+        options: &ZoneQueryOptions,
+        bbox: Option<Vec<Vec<f64>>>,
+    ) -> Zones {
+        let n = 1u32 << depth;
+        let bbox = bbox.map(|b| Rect::new((b[0][0], b[0][1]), (b[1][0], b[1][1])));
+        let mut zones = Zones::new();
+        for y in 0..n {
+            for x in 0..n {
+                let rect = Self::rect(depth, x, y);
+                let west = Rect::new(
+                    (rect.min().x - 360.0, rect.min().y),
+                    (rect.max().x - 360.0, rect.max().y),
+                );
+                if bbox.is_none_or(|b| b.intersects(&rect) || b.intersects(&west)) {
+                    zones.push(Self::zone(depth, x, y, options));
+                }
+            }
+        }
+        zones
+    }
+
+    fn zone_from_point(&self, depth: u8, point: Point, options: &ZoneQueryOptions) -> Zones {
+        let n = 1i64 << depth;
+        let (w, h) = (360.0 / n as f64, 180.0 / n as f64);
+        let x = ((point.x() + 180.0 - Self::shift(depth)) / w).floor() as i64;
+        let y = ((point.y() + 90.0) / h) as i64;
+        vec![Self::zone(
+            depth,
+            x.rem_euclid(n) as u32,
+            y.min(n - 1) as u32,
+            options,
+        )]
+        .into()
+    }
+
+    /// The zones at `depth` overlapping the parent.
+    fn zones_from_parent(
+        &self,
+        depth: u8,
+        parent_zone_id: String,
+        options: &ZoneQueryOptions,
+    ) -> Zones {
+        let (d, px, py) = QuadGrid::cell(&parent_zone_id);
+        let parent = Self::rect(d, px, py);
+        let n = 1i64 << depth;
+        let k = 1u32 << (depth - d);
+        let w = 360.0 / n as f64;
+        let first = ((parent.min().x + 180.0 - Self::shift(depth)) / w).floor() as i64;
+        let last = ((parent.max().x + 180.0 - Self::shift(depth)) / w).ceil() as i64;
+        let mut zones = Zones::new();
+        for cy in py * k..(py + 1) * k {
+            for cx in first..last {
+                zones.push(Self::zone(depth, cx.rem_euclid(n) as u32, cy, options));
+            }
+        }
+        zones
+    }
+
+    fn zone_from_id(&self, zone_id: String, options: &ZoneQueryOptions) -> Zones {
+        let (d, x, y) = QuadGrid::cell(&zone_id);
+        vec![Self::zone(d, x, y, options)].into()
+    }
+
+    fn max_depth(&self) -> u8 {
+        20
+    }
+
+    fn resolution_info(&self, depth: u8) -> ResolutionInfo {
+        QuadGrid.resolution_info(depth)
+    }
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::QuadGrid;
+use geo_plegmata::coverage::Coverage;
+use std::sync::Arc;
+
+#[test]
+fn test_coverage_blocks_and_values() {
+    let mut coverage: Coverage<f64> = Coverage::new("QUAD", Arc::new(QuadGrid), 3, 1).unwrap();
+    let block = QuadGrid::id(1, 1, 0);
+    coverage.add_block(&block).unwrap();
+
+    assert_eq!(coverage.dggrs(), "QUAD");
+    assert_eq!(coverage.value_type(), "f64");
+    assert_eq!((coverage.resolution(), coverage.zone_resolution()), (3, 1));
+    assert_eq!(coverage.zone_ids(), vec![block.clone()]);
+    assert_eq!(coverage.len(), 16);
+
+    let cell = QuadGrid::id(3, 5, 2);
+    assert_eq!(coverage.set(&cell, 2.5).unwrap(), None);
+    assert_eq!(coverage.get(&cell), Some(&2.5));
+    assert!(coverage.set(&QuadGrid::id(3, 0, 0), 1.0).is_err());
+
+    let values = coverage.get_zone(&block).unwrap();
+    let position = coverage
+        .zone_cells(&block)
+        .unwrap()
+        .iter()
+        .position(|id| *id == cell)
+        .unwrap();
+    assert_eq!(values[position], Some(2.5));
+    assert_eq!(values.iter().flatten().count(), 1);
+    assert_eq!(coverage.iter().count(), 1);
+}
+
+#[test]
+fn test_coverage_validates_depths() {
+    let grid = Arc::new(QuadGrid);
+
+    assert!(Coverage::<u8>::new("QUAD", grid.clone(), 3, 3).is_err());
+    assert!(Coverage::<u8>::new("QUAD", grid.clone(), 21, 2).is_err());
+
+    let mut coverage = Coverage::<u8>::new("QUAD", grid, 3, 1).unwrap();
+    assert!(coverage.add_block(&QuadGrid::id(2, 0, 0)).is_err());
+    coverage
+        .add_blocks_in_bbox(Some(vec![vec![-170.0, -80.0], vec![-10.0, -10.0]]))
+        .unwrap();
+    assert_eq!(coverage.zone_ids().len(), 1);
+}
@@ -0,0 +1,50 @@
+use geo_plegmata::adapters::dggrid::{igeo7::Igeo7Impl, isea3h::Isea3hImpl};
+use geo_plegmata::dggrs::resolution::{
+    depth_for_area, depth_for_spacing, depth_for_zoom, equivalent_depth,
+};
+use geo_plegmata::ports::dggrs::DggrsPort;
+
+#[test]
+fn test_depth_for_area() {
+    let isea3h = Isea3hImpl::default();
+
+    for depth in [0, 5, 9, 17] {
+        let area = isea3h.resolution_info(depth).mean_area;
+        assert_eq!(depth_for_area(&isea3h, area), Some(depth));
+        assert_eq!(depth_for_area(&isea3h, area * 1.2), Some(depth));
+    }
+    assert_eq!(depth_for_area(&isea3h, 1e12), Some(0));
+    assert_eq!(depth_for_area(&isea3h, 1e-12), Some(isea3h.max_depth()));
+    for invalid in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert_eq!(depth_for_area(&isea3h, invalid), None);
+    }
+}
+
+#[test]
+fn test_depth_for_spacing() {
+    let igeo7 = Igeo7Impl::default();
+    let spacing = igeo7.resolution_info(8).spacing;
+
+    assert_eq!(depth_for_spacing(&igeo7, spacing * 0.9), Some(8));
+}
+
+#[test]
+fn test_depth_for_zoom_refines_with_zoom() {
+    let igeo7 = Igeo7Impl::default();
+    let coarse = depth_for_zoom(&igeo7, 3, 45.0, 20.0);
+    let fine = depth_for_zoom(&igeo7, 12, 45.0, 20.0);
+
+    assert!(coarse < fine);
+    assert_eq!(depth_for_zoom(&igeo7, 3, 45.0, 0.0), None);
+}
+
+#[test]
+fn test_equivalent_depth() {
+    let isea3h = Isea3hImpl::default();
+    let igeo7 = Igeo7Impl::default();
+
+    // 3^9 is closest to 7^5 (19683 vs 16807)
+    assert_eq!(equivalent_depth(&isea3h, 9, &igeo7), Some(5));
+    assert_eq!(equivalent_depth(&igeo7, 5, &isea3h), Some(9));
+    assert_eq!(equivalent_depth(&isea3h, 4, &isea3h), Some(4));
+}
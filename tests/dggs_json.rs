@@ -0,0 +1,111 @@
+#![cfg(feature = "dggs-json")]
+
+use geo_plegmata::encoding::dggs_json::{DggsJson, Dimension, DimensionGrid};
+use geo_plegmata::models::common::ZoneID;
+use std::collections::HashMap;
+
+fn ids(n: u8) -> Vec<ZoneID> {
+    (0..n)
+        .map(|i| ZoneID::new(&format!("0500000000000000{:02}", i)).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_encode_scalar_values() {
+    let sub_zones = ids(3);
+    let values: HashMap<ZoneID, f64> = [(sub_zones[0].clone(), 1.5), (sub_zones[2].clone(), 3.0)]
+        .into_iter()
+        .collect();
+
+    let mut dggs_json = DggsJson::new("IGEO7", "040000000000000000");
+    dggs_json
+        .insert_scalar("temperature", 1, &sub_zones, &values)
+        .unwrap();
+
+    let json: serde_json::Value =
+        serde_json::from_str(&dggs_json.to_json_string().unwrap()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "dggrs": "IGEO7",
+            "zoneId": "040000000000000000",
+            "depths": [1],
+            "values": {
+                "temperature": [{
+                    "depth": 1,
+                    "shape": { "count": 3, "subZones": 3 },
+                    "data": [1.5, null, 3.0]
+                }]
+            }
+        })
+    );
+}
+
+#[test]
+fn test_round_trip_with_dimensions() {
+    let sub_zones = ids(2);
+    let mut dggs_json = DggsJson::new("ISEA3H", "040000000000000000");
+    dggs_json.dimensions.push(Dimension {
+        name: "time".to_string(),
+        interval: None,
+        grid: DimensionGrid {
+            cells_count: 2,
+            coordinates: vec![],
+        },
+    });
+    let values: HashMap<ZoneID, Vec<Option<f64>>> = [
+        (sub_zones[0].clone(), vec![Some(1.0), Some(2.0)]),
+        (sub_zones[1].clone(), vec![None, Some(4.0)]),
+    ]
+    .into_iter()
+    .collect();
+    dggs_json.insert("rain", 2, &sub_zones, &values).unwrap();
+
+    let decoded = DggsJson::from_json_str(&dggs_json.to_json_string().unwrap()).unwrap();
+    assert_eq!(decoded, dggs_json);
+    assert_eq!(decoded.values["rain"][0].shape.dimensions["time"], 2);
+    assert_eq!(decoded.get("rain", 2, &sub_zones).unwrap(), values);
+    assert!(decoded.get("rain", 2, &sub_zones[..1]).is_err());
+    assert!(
+        dggs_json
+            .insert(
+                "rain",
+                3,
+                &sub_zones,
+                &[(sub_zones[0].clone(), vec![Some(1.0)])].into()
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn test_decode_foreign_zone_ids() {
+    let data = r#"{
+        "dggrs": "https://www.opengis.net/def/dggrs/OGC/1.0/ISEA3H",
+        "zoneId": "5-24-6E",
+        "depths": [1],
+        "values": {
+            "rain": [{ "depth": 1, "shape": { "count": 2, "subZones": 2 }, "data": [1.0, 2.0] }]
+        }
+    }"#;
+    let decoded = DggsJson::from_json_str(data).unwrap();
+    assert_eq!(decoded.zone_id, "5-24-6E");
+    assert!(decoded.zone().is_err());
+    assert_eq!(
+        decoded.get("rain", 1, &ids(2)).unwrap()[&ids(2)[1]],
+        vec![Some(2.0)]
+    );
+
+    // Three sub-zones announced, but only two values given
+    let short = data.replace(
+        "\"count\": 2, \"subZones\": 2",
+        "\"count\": 3, \"subZones\": 3",
+    );
+    assert!(DggsJson::from_json_str(&short).is_err());
+    let short = data.replace("[1.0, 2.0]", "[1.0]");
+    assert!(DggsJson::from_json_str(&short).is_err());
+
+    let mut decoded = decoded;
+    decoded.values.get_mut("rain").unwrap()[0].data.pop();
+    assert!(decoded.get("rain", 1, &ids(2)).is_err());
+}
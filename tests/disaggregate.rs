@@ -0,0 +1,61 @@
+mod common;
+
+use common::QuadGrid;
+use geo_plegmata::coverage::Coverage;
+use geo_plegmata::coverage::disaggregate::Disaggregation;
+use std::sync::Arc;
+
+fn coverage(depth: u8) -> Coverage<f64> {
+    let mut coverage = Coverage::new("QUAD", Arc::new(QuadGrid), depth, 1).unwrap();
+    coverage.add_block(&QuadGrid::id(1, 1, 0)).unwrap();
+    coverage
+}
+
+fn children() -> Vec<geo_plegmata::models::common::ZoneID> {
+    [(4, 0), (5, 0), (4, 1), (5, 1)]
+        .iter()
+        .map(|&(x, y)| QuadGrid::id(3, x, y))
+        .collect()
+}
+
+#[test]
+fn test_copy_and_area_split() {
+    let mut population = coverage(2);
+    population.set(&QuadGrid::id(2, 2, 0), 8.0).unwrap();
+
+    let copied = population
+        .disaggregate(3, Disaggregation::Copy, None)
+        .unwrap();
+    assert_eq!(copied.iter().count(), 4);
+    for id in children() {
+        assert!((copied.get(&id).unwrap() - 8.0).abs() < 1e-9);
+    }
+
+    let split = population
+        .disaggregate(3, Disaggregation::AreaSplit, None)
+        .unwrap();
+    let total: f64 = split.iter().map(|(_, value)| value).sum();
+    assert!((total - 8.0).abs() < 1e-9);
+    // Zones closer to the pole are smaller and get less
+    assert!(split.get(&children()[0]).unwrap() < split.get(&children()[2]).unwrap());
+}
+
+#[test]
+fn test_weighted_split() {
+    let mut population = coverage(2);
+    population.set(&QuadGrid::id(2, 2, 0), 8.0).unwrap();
+    let mut built_up = coverage(3);
+    built_up.set(&children()[1], 1.0).unwrap();
+
+    let split = population
+        .disaggregate(3, Disaggregation::AreaSplit, Some(&built_up))
+        .unwrap();
+    assert!((split.get(&children()[1]).unwrap() - 8.0).abs() < 1e-9);
+    assert_eq!(split.get(&children()[0]), Some(&0.0));
+
+    assert!(
+        population
+            .disaggregate(3, Disaggregation::AreaSplit, Some(&population))
+            .is_err()
+    );
+}
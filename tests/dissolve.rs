@@ -0,0 +1,98 @@
+use geo::{LineString, Point, Polygon};
+use geo_plegmata::models::common::{Zone, ZoneID, Zones};
+
+/// Unit square zone with its lower left corner at (x, y) in a grid of squares.
+fn square(x: i32, y: i32) -> Zone {
+    let (fx, fy) = (x as f64, y as f64);
+    Zone {
+        id: id(x, y),
+        region: Polygon::new(
+            LineString::from(vec![
+                (fx, fy),
+                (fx + 1.0, fy),
+                (fx + 1.0, fy + 1.0),
+                (fx, fy + 1.0),
+                (fx, fy),
+            ]),
+            vec![],
+        ),
+        center: Point::new(fx + 0.5, fy + 0.5),
+        vertex_count: 4,
+        children: vec![],
+        neighbors: (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (dx, dy)))
+            .filter(|&d| d != (0, 0))
+            .map(|(dx, dy)| id(x + dx, y + dy))
+            .collect(),
+    }
+}
+
+fn id(x: i32, y: i32) -> ZoneID {
+    ZoneID::new(&format!("05{:08}{:08}", x + 100, y + 100)).unwrap()
+}
+
+#[test]
+fn test_dissolve_block() {
+    let zones: Zones = (0..3)
+        .flat_map(|x| (0..3).map(move |y| square(x, y)))
+        .collect();
+    let outline = zones.dissolve();
+
+    assert_eq!(outline.0.len(), 1);
+    assert!(outline.0[0].interiors().is_empty());
+    // 3 vertices along each side of the block, plus the closing one
+    assert_eq!(outline.0[0].exterior().0.len(), 13);
+}
+
+#[test]
+fn test_dissolve_ring_keeps_hole() {
+    let zones: Zones = (0..3)
+        .flat_map(|x| (0..3).map(move |y| (x, y)))
+        .filter(|&c| c != (1, 1))
+        .map(|(x, y)| square(x, y))
+        .collect();
+    let outline = zones.dissolve();
+
+    assert_eq!(outline.0.len(), 1);
+    assert_eq!(outline.0[0].interiors().len(), 1);
+}
+
+#[test]
+fn test_dissolve_without_topology() {
+    let mut zones: Zones = vec![square(0, 0), square(1, 0), square(5, 5)].into();
+    zones = zones
+        .into_iter()
+        .map(|mut zone| {
+            zone.neighbors.clear();
+            zone
+        })
+        .collect();
+
+    assert_eq!(zones.dissolve().0.len(), 2);
+}
+
+#[test]
+fn test_dissolve_pinched_corners() {
+    // Diagonal squares only touch at their corners
+    for _ in 0..10 {
+        let zones: Zones = vec![square(0, 0), square(1, 1), square(2, 0)].into();
+        let outline = zones.dissolve();
+
+        assert_eq!(outline.0.len(), 3);
+        for polygon in &outline.0 {
+            assert_eq!(polygon.exterior().0.len(), 5);
+            assert!(polygon.interiors().is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_dissolve_mixed_topology() {
+    // One zone without neighbours falls back to matching all edges
+    let mut zones: Vec<Zone> = (0..2).map(|x| square(x, 0)).collect();
+    zones[1].neighbors.clear();
+    let outline = Zones::from(zones).dissolve();
+
+    assert_eq!(outline.0.len(), 1);
+    assert_eq!(outline.0[0].exterior().0.len(), 7);
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::QuadGrid;
+use geo_plegmata::coverage::Coverage;
+use geo_plegmata::coverage::focal::{Decay, Focal};
+use std::sync::Arc;
+
+/// Depth 3 cells of block (1, 1, 0), a 4 by 4 square, valued x + 10 y.
+fn coverage() -> Coverage<f64> {
+    let mut coverage = Coverage::new("QUAD", Arc::new(QuadGrid), 3, 1).unwrap();
+    coverage.add_block(&QuadGrid::id(1, 1, 0)).unwrap();
+    for x in 4..8 {
+        for y in 0..4 {
+            coverage
+                .set(&QuadGrid::id(3, x, y), (x + 10 * y) as f64)
+                .unwrap();
+        }
+    }
+    coverage
+}
+
+#[test]
+fn test_focal_statistics() {
+    let coverage = coverage();
+    let inner = QuadGrid::id(3, 5, 1);
+    let corner = QuadGrid::id(3, 4, 0);
+    let focal = |statistic| coverage.focal(1, statistic, Decay::Uniform).unwrap();
+
+    assert_eq!(focal(Focal::Mean).get(&inner), Some(&15.0));
+    assert_eq!(focal(Focal::Sum).get(&inner), Some(&135.0));
+    assert_eq!(focal(Focal::Min).get(&inner), Some(&4.0));
+    assert_eq!(focal(Focal::Max).get(&inner), Some(&26.0));
+    // The corner only has three neighbours within the coverage
+    assert_eq!(
+        focal(Focal::Sum).get(&corner),
+        Some(&(4.0 + 5.0 + 14.0 + 15.0))
+    );
+    let stddev = *focal(Focal::StdDev).get(&inner).unwrap();
+    assert!((stddev - (202.0_f64 / 3.0).sqrt()).abs() < 1e-9);
+}
+
+#[test]
+fn test_decay_and_kernel() {
+    let mut coverage = coverage();
+    let inner = QuadGrid::id(3, 5, 1);
+    coverage.set(&inner, 115.0).unwrap();
+
+    let uniform = coverage.focal(1, Focal::Mean, Decay::Uniform).unwrap();
+    let decayed = coverage
+        .focal(1, Focal::Mean, Decay::InverseRing(2.0))
+        .unwrap();
+    assert!(decayed.get(&inner) > uniform.get(&inner));
+
+    let kernel = coverage.focal_kernel(&[1.0, 0.0, 0.0]).unwrap();
+    assert_eq!(kernel.get(&inner), Some(&115.0));
+    assert!(coverage.focal_kernel(&[]).is_err());
+}
+
+#[test]
+fn test_neighbourhood_crosses_gaps() {
+    // Blocks (2, 1, 0) and (2, 3, 0) leave the cells at x 4 and 5 out
+    let mut coverage = Coverage::new("QUAD", Arc::new(QuadGrid), 3, 2).unwrap();
+    coverage.add_block(&QuadGrid::id(2, 1, 0)).unwrap();
+    coverage.add_block(&QuadGrid::id(2, 3, 0)).unwrap();
+    let cell = QuadGrid::id(3, 3, 0);
+    coverage.set(&cell, 1.0).unwrap();
+    coverage.set(&QuadGrid::id(3, 6, 0), 10.0).unwrap();
+
+    let sums = |radius| coverage.focal(radius, Focal::Sum, Decay::Uniform).unwrap();
+    assert_eq!(sums(2).get(&cell), Some(&1.0));
+    assert_eq!(sums(3).get(&cell), Some(&11.0));
+}
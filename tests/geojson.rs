@@ -0,0 +1,94 @@
+#![cfg(feature = "geojson")]
+
+use geo::{Area, LineString, Point, Polygon, Winding};
+use geo_plegmata::encoding::geojson;
+use geo_plegmata::models::common::{Zone, ZoneID, Zones};
+
+fn zone(id: &str, coords: Vec<(f64, f64)>) -> Zone {
+    let region = Polygon::new(LineString::from(coords), vec![]);
+    Zone {
+        id: ZoneID::new(id).unwrap(),
+        vertex_count: region.exterior().0.len() as u32 - 1,
+        center: Point::new(0.0, 0.0),
+        region,
+        children: vec![ZoneID::new("0500110000000000ff").unwrap()],
+        neighbors: vec![],
+    }
+}
+
+#[test]
+fn test_feature_properties() {
+    // clockwise ring
+    let zones: Zones = vec![zone(
+        "0400100000000000ff",
+        vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0), (0.0, 0.0)],
+    )]
+    .into();
+    let collection = geojson::to_feature_collection(&zones, "IGEO7");
+    let feature = &collection.features[0];
+
+    assert_eq!(feature.property("zone_id").unwrap(), "0400100000000000ff");
+    assert_eq!(feature.property("depth").unwrap(), 4);
+    assert_eq!(feature.property("dggrs").unwrap(), "IGEO7");
+    assert_eq!(feature.property("vertex_count").unwrap(), 4);
+    assert_eq!(
+        feature.property("children").unwrap(),
+        &serde_json::json!(["0500110000000000ff"])
+    );
+
+    let polygon: Polygon = feature.geometry.clone().unwrap().try_into().unwrap();
+    assert!(polygon.exterior().is_ccw());
+}
+
+#[test]
+fn test_antimeridian_split_and_join() {
+    let zones: Zones = vec![zone(
+        "0400100000000000ff",
+        vec![
+            (179.0, -1.0),
+            (181.0, -1.0),
+            (181.0, 1.0),
+            (179.0, 1.0),
+            (179.0, -1.0),
+        ],
+    )]
+    .into();
+    let json = geojson::to_string(&zones, "IGEO7");
+    assert!(json.contains("MultiPolygon"));
+
+    let back = geojson::from_str(&json).unwrap();
+    let zone = back
+        .get(&ZoneID::new("0400100000000000ff").unwrap())
+        .unwrap();
+    assert!((zone.region.unsigned_area() - 4.0).abs() < 1e-9);
+    assert_eq!(zone.children.len(), 1);
+    assert_eq!(zone.vertex_count, 4);
+}
+
+#[test]
+fn test_streamed_output_matches_string() {
+    let zones: Zones = vec![
+        zone(
+            "0400100000000000ff",
+            vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)],
+        ),
+        zone(
+            "0400200000000000ff",
+            vec![(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 0.0)],
+        ),
+    ]
+    .into();
+    let mut buffer = Vec::new();
+    geojson::write(zones.clone(), "ISEA3H", &mut buffer).unwrap();
+
+    let streamed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+    let expected: serde_json::Value =
+        serde_json::from_str(&geojson::to_string(&zones, "ISEA3H")).unwrap();
+    assert_eq!(streamed, expected);
+    assert_eq!(
+        geojson::from_str(&geojson::to_string(&zones, "ISEA3H"))
+            .unwrap()
+            .len(),
+        2
+    );
+}
@@ -0,0 +1,87 @@
+use geo::{LineString, Point, Polygon};
+use geo_plegmata::encoding::gpkg;
+use geo_plegmata::models::common::{Zone, ZoneID};
+use rusqlite::Connection;
+
+fn zone(id: &str, x: f64) -> Zone {
+    Zone {
+        id: ZoneID::new(id).unwrap(),
+        region: Polygon::new(
+            LineString::from(vec![(x, 0.0), (x + 2.0, 0.0), (x + 2.0, 1.0), (x, 0.0)]),
+            vec![],
+        ),
+        center: Point::new(x + 1.0, 0.5),
+        vertex_count: 3,
+        children: vec![],
+        neighbors: vec![ZoneID::new("0400300000000000ff").unwrap()],
+    }
+}
+
+#[test]
+fn test_write_geopackage() {
+    let path = std::env::temp_dir().join("gp_zones.gpkg");
+    let _ = std::fs::remove_file(&path);
+    let zones = vec![
+        zone("0400100000000000ff", 0.0),
+        zone("0400200000000000ff", 179.0),
+    ];
+    gpkg::write(zones, &path, "igeo7_4", "IGEO7", "DGGRID").unwrap();
+
+    let conn = Connection::open(&path).unwrap();
+    let query = |sql: &str| -> String { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+    let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+
+    assert_eq!(count("PRAGMA application_id"), 0x4750_4B47);
+    assert_eq!(count("SELECT srs_id FROM gpkg_geometry_columns"), 4326);
+    assert_eq!(count("SELECT count(*) FROM gpkg_spatial_ref_sys"), 3);
+    assert_eq!(count("SELECT count(*) FROM rtree_igeo7_4_geom"), 2);
+    assert_eq!(
+        query("SELECT neighbors FROM igeo7_4 WHERE depth = 4 LIMIT 1"),
+        "0400300000000000ff"
+    );
+    assert!(query("SELECT metadata FROM gpkg_metadata").contains("\"dggrs\":\"IGEO7\""));
+
+    // The second zone crosses the antimeridian, its envelope spans the globe
+    let blob: Vec<u8> = conn
+        .query_row("SELECT geom FROM igeo7_4 WHERE fid = 2", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(&blob[..4], b"GP\x00\x03");
+    assert_eq!(blob[40 + 1], 6); // WKB multi-polygon
+    assert_eq!(f64::from_le_bytes(blob[8..16].try_into().unwrap()), -180.0);
+
+    // A second table shares the metadata extension rows
+    drop(conn);
+    gpkg::write(
+        vec![zone("0500100000000000ff", 0.0)],
+        &path,
+        "igeo7_5",
+        "IGEO7",
+        "DGGRID \"8.3\" C:\\dggrid\t",
+    )
+    .unwrap();
+    let conn = Connection::open(&path).unwrap();
+    let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+    // Quotes, backslashes and control characters are escaped
+    let metadata: String = conn
+        .query_row(
+            "SELECT metadata FROM gpkg_metadata WHERE id = 2",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    let metadata: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+    assert_eq!(metadata["backend"], "DGGRID \"8.3\" C:\\dggrid\t");
+    assert_eq!(
+        count("SELECT count(*) FROM gpkg_extensions WHERE extension_name = 'gpkg_metadata'"),
+        2
+    );
+    assert_eq!(
+        count("SELECT count(*) FROM gpkg_extensions WHERE extension_name = 'gpkg_rtree_index'"),
+        2
+    );
+
+    assert!(gpkg::write(vec![], &path, "igeo7_4", "IGEO7", "DGGRID").is_err());
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,53 @@
+#![cfg(unix)]
+
+use geo::Point;
+use geo_plegmata::adapters::dggrid::igeo7::Igeo7Impl;
+use geo_plegmata::adapters::dggrid::isea3h::Isea3hImpl;
+use geo_plegmata::models::common::ZoneQueryOptions;
+use geo_plegmata::ports::dggrs::DggrsPort;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+/// A stand-in for DGGRID's TRANSFORM_POINTS, writing for each input line the
+/// first coordinate plus 180 as the address, followed by the coordinates.
+const FAKE_DGGRID: &str = r#"#!/bin/sh
+input=$(awk '$1 == "input_file_name" { print $2 }' "$1")
+output=$(awk '$1 == "output_file_name" { print $2 }' "$1")
+if [ -z "$output" ]; then
+    output="$(awk '$1 == "point_output_file_name" { print $2 }' "$1").gen"
+fi
+awk '{ printf "%016x %s %s\n", $1 + 180, $1, $2 }' "$input" > "$output"
+"#;
+
+fn fake_dggrid(name: &str) -> (PathBuf, PathBuf) {
+    let workdir = std::env::temp_dir().join(name);
+    fs::create_dir_all(&workdir).unwrap();
+    let executable = workdir.join("dggrid");
+    fs::write(&executable, FAKE_DGGRID).unwrap();
+    fs::set_permissions(&executable, fs::Permissions::from_mode(0o755)).unwrap();
+    (executable, workdir)
+}
+
+#[test]
+fn test_single_and_batch_point_lookups_agree() {
+    let (executable, workdir) = fake_dggrid("gp_point_lookup_test");
+    let dggrss: [Box<dyn DggrsPort>; 2] = [
+        Box::new(Igeo7Impl::new(executable.clone(), workdir.clone())),
+        Box::new(Isea3hImpl::new(executable, workdir.clone())),
+    ];
+    let points = [Point::new(100.0, 10.0), Point::new(-60.0, -30.0)];
+
+    for dggrs in &dggrss {
+        let batch = dggrs.zone_ids_from_points(5, &points).unwrap();
+        for (point, id) in points.iter().zip(batch) {
+            let single = dggrs.zone_from_point(5, *point, &ZoneQueryOptions::centroid_only());
+            let zone = single.iter().next().unwrap();
+            assert_eq!(Some(zone.id.clone()), id);
+            // Both paths give DGGRID the longitude first
+            assert_eq!(zone.center, *point);
+            assert_eq!(zone.id.id, format!("05{:016x}", (point.x() + 180.0) as u64));
+        }
+    }
+    let _ = fs::remove_dir_all(&workdir);
+}
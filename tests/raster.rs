@@ -0,0 +1,75 @@
+mod common;
+
+use common::{QuadGrid, ShiftedGrid};
+use geo_plegmata::coverage::Coverage;
+use geo_plegmata::coverage::raster::{Raster, Resampling};
+use std::sync::Arc;
+
+/// 8 by 8 pixels of 22.5° by 11.25° over lon 0 to 180 and lat -90 to 0, valued
+/// col + 10 row, so every depth 3 quad zone there covers 2 by 2 pixels.
+fn raster() -> Raster {
+    let data = (0..8)
+        .flat_map(|row| (0..8).map(move |col| (col + 10 * row) as f64))
+        .collect();
+    Raster::new(8, 8, [0.0, 22.5, 0.0, 0.0, 0.0, -11.25], data).unwrap()
+}
+
+fn sample(raster: &Raster, method: Resampling) -> Coverage<f64> {
+    Coverage::from_raster("QUAD", Arc::new(QuadGrid), 3, 1, raster, method).unwrap()
+}
+
+#[test]
+fn test_resampling_methods() {
+    let raster = raster();
+    // lon 0 to 45, lat -90 to -67.5, pixels col 0 to 1 and row 6 to 7
+    let zone = QuadGrid::id(3, 4, 0);
+
+    let nearest = sample(&raster, Resampling::Nearest);
+    assert!(nearest.zone_ids().contains(&QuadGrid::id(1, 1, 0)));
+    assert_eq!(nearest.get(&zone), Some(&71.0));
+
+    let bilinear = sample(&raster, Resampling::Bilinear);
+    assert_eq!(bilinear.get(&zone), Some(&65.5));
+
+    // The pixels of row 7, closer to the pole, are smaller
+    let weighted = *sample(&raster, Resampling::AreaWeighted)
+        .get(&zone)
+        .unwrap();
+    assert!(weighted > 60.5 && weighted < 65.5);
+}
+
+#[test]
+fn test_nodata_and_wrapping() {
+    let mut raster = raster().with_nodata(71.0);
+    assert_eq!(raster.get(1, 7), None);
+    let nearest = sample(&raster, Resampling::Nearest);
+    assert_eq!(nearest.get(&QuadGrid::id(3, 4, 0)), None);
+
+    // The same raster given from 180° to 360°, covering the western hemisphere
+    raster.geotransform[0] = 180.0;
+    let nearest = sample(&raster, Resampling::Nearest);
+    assert_eq!(nearest.get(&QuadGrid::id(3, 0, 1)), Some(&51.0));
+    // lon -180 to -135, lat -67.5 to -45, pixels col 0 to 1 and row 4 to 5
+    let weighted = sample(&raster, Resampling::AreaWeighted);
+    let value = *weighted.get(&QuadGrid::id(3, 0, 1)).unwrap();
+    assert!(value > 40.5 && value < 45.5, "{}", value);
+    // Every zone over the raster, the one whose nearest pixel is nodata included
+    assert_eq!(nearest.iter().count(), 15);
+    assert_eq!(weighted.iter().count(), 16);
+
+    // Given from -180° to 0°, the zone at lon 146.25° to 191.25° overlaps the first column
+    // with its part west of the antimeridian
+    raster.geotransform[0] = -180.0;
+    let shifted = Coverage::from_raster(
+        "SHIFTED",
+        Arc::new(ShiftedGrid),
+        3,
+        1,
+        &raster,
+        Resampling::AreaWeighted,
+    )
+    .unwrap();
+    let value = *shifted.get(&QuadGrid::id(3, 7, 1)).unwrap();
+    assert!(value > 40.0 && value < 45.0, "{}", value);
+    assert!(Raster::new(2, 2, [0.0; 6], vec![0.0; 4]).is_err());
+}
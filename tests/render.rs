@@ -0,0 +1,93 @@
+mod common;
+
+use common::QuadGrid;
+use geo::{Rect, coord};
+use geo_plegmata::adapters::dggrid::igeo7::Igeo7Impl;
+use geo_plegmata::coverage::Coverage;
+use geo_plegmata::coverage::raster::{Projection, Raster};
+use geo_plegmata::encoding::{ascii_grid, geotiff};
+use std::sync::Arc;
+
+/// The depth 2 quad zones over lon 0 to 180 and lat 0 to 90, valued x + 10 y,
+/// except the one at x 3, y 3 left without value.
+fn coverage() -> Coverage<f64> {
+    let mut coverage = Coverage::new("QUAD", Arc::new(QuadGrid), 2, 1).unwrap();
+    coverage.add_block(&QuadGrid::id(1, 1, 1)).unwrap();
+    for (x, y, value) in [(2, 2, 22.0), (3, 2, 23.0), (2, 3, 32.0)] {
+        coverage.set(&QuadGrid::id(2, x, y), value).unwrap();
+    }
+    coverage
+}
+
+fn render() -> Raster {
+    let extent = Rect::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 180.0, y: 90.0 });
+    coverage()
+        .to_raster(4, 2, extent, Projection::Geographic, -1.0)
+        .unwrap()
+}
+
+#[test]
+fn test_to_raster() {
+    let raster = render();
+    assert_eq!(raster.geotransform, [0.0, 45.0, 0.0, 90.0, 0.0, -45.0]);
+    assert_eq!(raster.nodata, Some(-1.0));
+    // The first row is the northern one
+    assert_eq!(
+        raster.data,
+        vec![32.0, 32.0, -1.0, -1.0, 22.0, 22.0, 23.0, 23.0]
+    );
+    assert_eq!(raster.get(2, 0), None);
+}
+
+#[test]
+fn test_to_raster_web_mercator() {
+    let extent = Rect::new(
+        coord! { x: 0.0, y: 0.0 },
+        coord! { x: 20_037_508.34, y: 20_037_508.34 },
+    );
+    let raster = coverage()
+        .to_raster(2, 2, extent, Projection::WebMercator, -1.0)
+        .unwrap();
+    // The centre of the upper left pixel is at lat 79.2, of the lower left at 41.0
+    assert_eq!(raster.data, vec![32.0, -1.0, 22.0, 23.0]);
+}
+
+#[test]
+fn test_to_raster_fails_without_dggrid() {
+    let igeo7 = Igeo7Impl::new("/nonexistent/dggrid".into(), std::env::temp_dir());
+    let coverage = Coverage::<f64>::new("IGEO7", Arc::new(igeo7), 3, 1).unwrap();
+    let extent = Rect::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 10.0, y: 10.0 });
+
+    let raster = coverage.to_raster(2, 2, extent, Projection::Geographic, -9999.0);
+    assert!(raster.unwrap_err().contains("DGGRID"));
+}
+
+#[test]
+fn test_write_ascii_grid() {
+    let mut buffer = vec![];
+    ascii_grid::write(&render(), &mut buffer).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert_eq!(
+        text,
+        "ncols 4\nnrows 2\nxllcorner 0\nyllcorner 0\ncellsize 45\nNODATA_value -1\n\
+         32 32 -1 -1\n22 22 23 23\n"
+    );
+}
+
+#[test]
+fn test_write_geotiff() {
+    let raster = render();
+    let mut buffer = vec![];
+    geotiff::write(&raster, &mut buffer).unwrap();
+    assert_eq!(&buffer[..4], b"II*\0");
+
+    let ifd = u32::from_le_bytes(buffer[4..8].try_into().unwrap()) as usize;
+    assert_eq!(ifd, 8 + 8 * raster.data.len());
+    let entries = u16::from_le_bytes(buffer[ifd..ifd + 2].try_into().unwrap());
+    assert_eq!(entries, 15);
+    // The pixel data follows the header
+    assert_eq!(f64::from_le_bytes(buffer[8..16].try_into().unwrap()), 32.0);
+
+    let rotated = Raster::new(1, 1, [0.0, 1.0, 0.5, 0.0, 0.0, -1.0], vec![0.0]).unwrap();
+    assert!(geotiff::write(&rotated, &mut vec![]).is_err());
+}
@@ -0,0 +1,35 @@
+use geo_plegmata::adapters::dggrid::{igeo7::Igeo7Impl, isea3h::Isea3hImpl};
+use geo_plegmata::models::common::EARTH_RADIUS_KM;
+use geo_plegmata::ports::dggrs::DggrsPort;
+
+#[test]
+fn test_zone_counts() {
+    let isea3h = Isea3hImpl::default();
+    let igeo7 = Igeo7Impl::default();
+
+    assert_eq!(isea3h.resolution_info(0).zone_count, 12);
+    assert_eq!(isea3h.resolution_info(1).zone_count, 32);
+    assert_eq!(isea3h.resolution_info(9).zone_count, 196832);
+    assert_eq!(igeo7.resolution_info(1).zone_count, 72);
+    assert_eq!(igeo7.resolution_info(5).zone_count, 168072);
+}
+
+#[test]
+fn test_areas_cover_the_globe() {
+    let earth_area = 4.0 * std::f64::consts::PI * EARTH_RADIUS_KM.powi(2);
+    for info in Igeo7Impl::default().resolution_table() {
+        let total = info.mean_area * info.zone_count as f64;
+        assert!((total - earth_area).abs() / earth_area < 1e-9);
+    }
+}
+
+#[test]
+fn test_resolution_table_is_ordered() {
+    let table = Isea3hImpl::default().resolution_table();
+
+    assert_eq!(table.len(), 31);
+    assert!(table.windows(2).all(|w| w[0].depth + 1 == w[1].depth
+        && w[0].mean_area > w[1].mean_area
+        && w[0].spacing > w[1].spacing
+        && w[0].cls > w[1].cls));
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "serde")]
+
+use geo::{LineString, Point, Polygon};
+use geo_plegmata::models::common::{Zone, ZoneID, Zones};
+use geo_plegmata::models::zone_set::ZoneSet;
+
+fn zone() -> Zone {
+    Zone {
+        id: ZoneID::new("0400100000000000ff").unwrap(),
+        region: Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]),
+            vec![],
+        ),
+        center: Point::new(0.5, 0.25),
+        vertex_count: 3,
+        children: vec![ZoneID::new("0500110000000000ff").unwrap()],
+        neighbors: vec![],
+    }
+}
+
+#[test]
+fn test_zone_representation() {
+    let json = serde_json::to_value(zone()).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "id": "0400100000000000ff",
+            "region": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]],
+            "center": [0.5, 0.25],
+            "vertex_count": 3,
+            "children": ["0500110000000000ff"]
+        })
+    );
+}
+
+#[test]
+fn test_zones_round_trip() {
+    let zones: Zones = vec![zone()].into();
+    let json = serde_json::to_string(&zones).unwrap();
+    let back: Zones = serde_json::from_str(&json).unwrap();
+
+    let zone = back
+        .get(&ZoneID::new("0400100000000000ff").unwrap())
+        .unwrap();
+    assert_eq!(zone.region.exterior().0.len(), 4);
+    assert_eq!(zone.center, Point::new(0.5, 0.25));
+    assert_eq!(zone.children.len(), 1);
+    assert!(zone.neighbors.is_empty());
+}
+
+#[test]
+fn test_invalid_zone_id_is_rejected() {
+    assert!(serde_json::from_str::<ZoneID>("\"not-an-id\"").is_err());
+}
+
+#[test]
+fn test_zone_set_is_compact_string() {
+    let set: ZoneSet = "4:00100000000000ff,00200000000000ff".parse().unwrap();
+    let json = serde_json::to_string(&set).unwrap();
+
+    assert_eq!(json, "\"4:00100000000000ff,00200000000000ff\"");
+    assert_eq!(serde_json::from_str::<ZoneSet>(&json).unwrap(), set);
+}
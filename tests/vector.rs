@@ -0,0 +1,138 @@
+mod common;
+
+use common::QuadGrid;
+use geo::{Geometry, LineString, MultiLineString, Point, Polygon, polygon};
+use geo_plegmata::vector::{AttributeValue, Vector, VectorGeometry};
+use std::sync::Arc;
+
+fn vector() -> Vector {
+    let fields = vec!["name".to_string(), "lanes".to_string()];
+    Vector::new("QUAD", Arc::new(QuadGrid), 3, fields).unwrap()
+}
+
+#[test]
+fn test_snap_line() {
+    let mut vector = vector();
+    // The first two vertices fall in the same depth 3 zone, 45° by 22.5°
+    let road = LineString::from(vec![(1.0, 1.0), (2.0, 2.0), (50.0, 1.0), (100.0, 30.0)]);
+    let index = vector
+        .push_geo(&road.into(), vec!["A1".into(), 2i64.into()])
+        .unwrap();
+
+    let expected = vec![
+        QuadGrid::id(3, 4, 4),
+        QuadGrid::id(3, 5, 4),
+        QuadGrid::id(3, 6, 5),
+    ];
+    assert_eq!(
+        vector.features()[index].geometry,
+        VectorGeometry::LineString(expected)
+    );
+    assert_eq!(
+        vector.attribute(index, "lanes"),
+        Some(&AttributeValue::Integer(2))
+    );
+    assert_eq!(vector.attribute(index, "speed"), None);
+
+    let Some(Geometry::LineString(line)) = vector.to_geo(index) else {
+        panic!("Expected a line");
+    };
+    let vertices: Vec<Point> = line.points().collect();
+    assert_eq!(
+        vertices,
+        vec![
+            Point::new(22.5, 11.25),
+            Point::new(67.5, 11.25),
+            Point::new(112.5, 33.75)
+        ]
+    );
+}
+
+#[test]
+fn test_snap_polygon() {
+    let mut vector = vector();
+    let area: Polygon = polygon![
+        (x: 10.0, y: 10.0),
+        (x: 100.0, y: 10.0),
+        (x: 100.0, y: 30.0),
+        (x: 10.0, y: 30.0),
+    ];
+    vector
+        .push_geo(&area.into(), vec![AttributeValue::Null, 0i64.into()])
+        .unwrap();
+    let VectorGeometry::Polygon(rings) = &vector.features()[0].geometry else {
+        panic!("Expected a polygon");
+    };
+    // The ring is stored open
+    assert_eq!(rings.len(), 1);
+    assert_eq!(rings[0].len(), 4);
+
+    let geometries = vector.geometries();
+    let Some(Geometry::Polygon(polygon)) = &geometries[0] else {
+        panic!("Expected a polygon");
+    };
+    assert_eq!(polygon.exterior().0.len(), 5);
+    assert!(polygon.exterior().is_closed());
+}
+
+#[test]
+fn test_push_validation() {
+    let mut vector = vector();
+    let point = VectorGeometry::Point(QuadGrid::id(2, 0, 0));
+    assert!(vector.push(point, vec!["a".into(), 1i64.into()]).is_err());
+    let point = VectorGeometry::Point(QuadGrid::id(3, 0, 0));
+    assert!(vector.push(point.clone(), vec!["a".into()]).is_err());
+    assert_eq!(vector.push(point, vec!["a".into(), 1i64.into()]), Ok(0));
+    assert_eq!(vector.len(), 1);
+}
+
+#[test]
+fn test_collapsed_geometries() {
+    let mut vector = vector();
+    let attributes = || vec![AttributeValue::Null, 0i64.into()];
+    // Within a single depth 3 zone
+    let small: Polygon = polygon![(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 2.0, y: 2.0)];
+    let short = LineString::from(vec![(1.0, 1.0), (2.0, 2.0)]);
+
+    assert!(
+        vector
+            .push_geo(&small.clone().into(), attributes())
+            .is_err()
+    );
+    assert!(
+        vector
+            .push_geo(&short.clone().into(), attributes())
+            .is_err()
+    );
+    let line = VectorGeometry::LineString(vec![QuadGrid::id(3, 4, 4)]);
+    assert!(vector.push(line, attributes()).is_err());
+
+    // Collapsed parts and holes are dropped
+    let road = LineString::from(vec![(1.0, 1.0), (50.0, 1.0)]);
+    let lines = MultiLineString::new(vec![short, road]);
+    let index = vector.push_geo(&lines.into(), attributes()).unwrap();
+    let VectorGeometry::MultiLineString(lines) = &vector.features()[index].geometry else {
+        panic!("Expected lines");
+    };
+    assert_eq!(lines.len(), 1);
+
+    let area = Polygon::new(
+        LineString::from(vec![
+            (10.0, 10.0),
+            (100.0, 10.0),
+            (100.0, 30.0),
+            (10.0, 30.0),
+        ]),
+        vec![LineString::from(vec![
+            (60.0, 15.0),
+            (61.0, 15.0),
+            (61.0, 16.0),
+        ])],
+    );
+    let index = vector.push_geo(&area.into(), attributes()).unwrap();
+    let VectorGeometry::Polygon(rings) = &vector.features()[index].geometry else {
+        panic!("Expected a polygon");
+    };
+    assert_eq!(rings.len(), 1);
+    assert_eq!(vector.depth(), 3);
+}
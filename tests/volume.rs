@@ -0,0 +1,56 @@
+mod common;
+
+use common::QuadGrid;
+use geo::Point;
+use geo_plegmata::types::{EARTH_RADIUS_M, ElevationId, VolumeId};
+
+#[test]
+fn test_elevation_id() {
+    // Layers of about 99.5 km at level 6
+    let thickness = ElevationId::thickness(6);
+    let above = ElevationId::from_altitude(250_000.0, 6).unwrap();
+    assert!(!above.is_below_sea_level());
+    assert_eq!((above.refinement_level(), above.index()), (6, 2));
+    assert_eq!(above.bounds(), (2.0 * thickness, 3.0 * thickness));
+    assert_eq!(above.bits(), 2 << 8 | 6 << 1);
+    assert_eq!(above.bit_length(), 32);
+
+    let below = ElevationId::from_altitude(-thickness, 6).unwrap();
+    assert!(below.is_below_sea_level());
+    assert_eq!(below.index(), 0);
+    assert_eq!(below.bounds(), (-thickness, 0.0));
+
+    assert!(ElevationId::from_altitude(EARTH_RADIUS_M, 6).is_err());
+    assert!(ElevationId::new(false, 6, 64).is_err());
+    assert_eq!(ElevationId::new(true, 60, 1).unwrap().bit_length(), 128);
+}
+
+#[test]
+fn test_vertical_navigation() {
+    let layer = ElevationId::from_altitude(-1_500.0, 12).unwrap();
+    let parent = layer.parent().unwrap();
+    assert_eq!(parent.refinement_level(), 11);
+    assert_eq!(parent.index(), layer.index() >> 1);
+    assert!(parent.children().unwrap().contains(&layer));
+
+    let [upper, lower] = layer.children().unwrap();
+    assert_eq!(upper.bounds().1, layer.bounds().1);
+    assert_eq!(lower.bounds().0, layer.bounds().0);
+    assert_eq!(upper.bounds().0, lower.bounds().1);
+    assert_eq!(ElevationId::new(false, 0, 0).unwrap().parent(), None);
+}
+
+#[test]
+fn test_volume_id() {
+    let volume = VolumeId::from_point(&QuadGrid, Point::new(10.0, 10.0), 8_000.0, 2, 10).unwrap();
+    assert_eq!(volume.zone, QuadGrid::id(2, 2, 2));
+
+    let bounds = volume.bounding_volume(&QuadGrid).unwrap();
+    assert!(bounds.bottom <= 8_000.0 && bounds.top > 8_000.0);
+    assert_eq!(bounds.region.exterior().0.len(), 5);
+
+    let [lower, upper] = volume.vertical_children().unwrap();
+    assert_eq!(lower.zone, volume.zone);
+    assert_eq!(upper.vertical_parent(), Some(volume));
+    assert_eq!(lower.elevation.bounds().0, bounds.bottom);
+}
@@ -0,0 +1,75 @@
+use geo::{LineString, Point, Polygon};
+use geo_plegmata::encoding::{wkb, wkt};
+use geo_plegmata::models::common::{Zone, ZoneID, Zones};
+
+fn zone(coords: Vec<(f64, f64)>) -> Zone {
+    Zone {
+        id: ZoneID::new("0400100000000000ff").unwrap(),
+        region: Polygon::new(LineString::from(coords), vec![]),
+        center: Point::new(0.5, 0.25),
+        vertex_count: 3,
+        children: vec![],
+        neighbors: vec![],
+    }
+}
+
+#[test]
+fn test_wkt() {
+    let zone = zone(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]);
+
+    assert_eq!(
+        wkt::region_to_wkt(&zone).unwrap(),
+        "POLYGON((0 0,1 0,1 1,0 0))"
+    );
+    assert_eq!(wkt::center_to_wkt(&zone), "POINT(0.5 0.25)");
+}
+
+#[test]
+fn test_wkt_splits_at_antimeridian() {
+    let zone = zone(vec![(179.0, 0.0), (181.0, 0.0), (181.0, 1.0), (179.0, 0.0)]);
+
+    assert!(
+        wkt::region_to_wkt(&zone)
+            .unwrap()
+            .starts_with("MULTIPOLYGON(((")
+    );
+}
+
+#[test]
+fn test_wkb_and_ewkb() {
+    let zone = zone(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]);
+
+    let point = wkb::center_to_wkb(&zone);
+    assert_eq!(point.len(), 21);
+    assert_eq!(&point[..5], &[1, 1, 0, 0, 0]);
+    assert_eq!(&point[5..13], &0.5_f64.to_le_bytes());
+
+    let ewkb = wkb::center_to_ewkb(&zone);
+    assert_eq!(&ewkb[..9], &[1, 1, 0, 0, 0x20, 0xE6, 0x10, 0, 0]);
+
+    let polygon = wkb::region_to_wkb(&zone).unwrap();
+    // header, ring count, point count and four points
+    assert_eq!(polygon.len(), 5 + 4 + 4 + 4 * 16);
+    let ewkb = wkb::region_to_ewkb(&zone).unwrap();
+    assert_eq!(ewkb.len(), polygon.len() + 4);
+    assert_eq!(&ewkb[1..5], &(3_u32 | 0x2000_0000).to_le_bytes());
+}
+
+#[test]
+fn test_tsv() {
+    let mut empty = zone(vec![]);
+    empty.id = ZoneID::new("0400200000000000ff").unwrap();
+    let zones: Zones = vec![
+        zone(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]),
+        empty,
+    ]
+    .into();
+    let mut buffer = Vec::new();
+    wkt::write_tsv(&zones, &mut buffer).unwrap();
+
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "0400100000000000ff\tPOLYGON((0 0,1 0,1 1,0 0))\tPOINT(0.5 0.25)\n\
+         0400200000000000ff\t\\N\tPOINT(0.5 0.25)\n"
+    );
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::{QuadGrid, ShiftedGrid};
+use geo::{MultiPolygon, Rect};
+use geo_plegmata::coverage::Coverage;
+use geo_plegmata::coverage::zonal::Membership;
+use std::sync::Arc;
+
+/// Depth 3 cells of block (1, 1, 0), 45° by 22.5° each, valued x + 10 y.
+fn coverage() -> Coverage<f64> {
+    let mut coverage = Coverage::new("QUAD", Arc::new(QuadGrid), 3, 1).unwrap();
+    coverage.add_block(&QuadGrid::id(1, 1, 0)).unwrap();
+    for x in 4..8 {
+        for y in 0..4 {
+            coverage
+                .set(&QuadGrid::id(3, x, y), (x + 10 * y) as f64)
+                .unwrap();
+        }
+    }
+    coverage
+}
+
+fn polygon(min: (f64, f64), max: (f64, f64)) -> MultiPolygon {
+    Rect::new(min, max).to_polygon().into()
+}
+
+#[test]
+fn test_zonal_statistics() {
+    let coverage = coverage();
+    let polygons = vec![
+        // Covers cells x 4 and 5 and 8/9 of x 6, centroids included, at y 0 to 1
+        polygon((0.0, -90.0), (130.0, -45.0)),
+        polygon((-100.0, 0.0), (-50.0, 10.0)),
+    ];
+
+    let by_centroid = coverage.zonal_statistics(&polygons, Membership::Centroid);
+    let stats = by_centroid[0].as_ref().unwrap();
+    assert_eq!(stats.count, 6);
+    assert_eq!(stats.sum, 4.0 + 5.0 + 6.0 + 14.0 + 15.0 + 16.0);
+    assert_eq!(stats.mean, 10.0);
+    assert_eq!((stats.min, stats.max), (4.0, 16.0));
+    assert_eq!(stats.median(), 10.0);
+    assert_eq!(stats.percentile(100.0), Some(16.0));
+    assert_eq!(stats.percentile(101.0), None);
+    assert!(by_centroid[1].is_none());
+
+    let by_area = coverage.zonal_statistics(&polygons, Membership::AreaFraction(0.9));
+    assert_eq!(by_area[0].as_ref().unwrap().count, 4);
+    let any_overlap = coverage.zonal_statistics(&polygons, Membership::AreaFraction(0.0));
+    assert_eq!(any_overlap[0].as_ref().unwrap().count, 6);
+}
+
+#[test]
+fn test_area_fraction_across_the_antimeridian() {
+    // Zone (1, 1, 0) spans longitudes 45 to 225, a quarter of it west of -135
+    let mut coverage = Coverage::new("SHIFTED", Arc::new(ShiftedGrid), 1, 0).unwrap();
+    coverage.add_block(&QuadGrid::id(0, 0, 0)).unwrap();
+    coverage.set(&QuadGrid::id(1, 1, 0), 5.0).unwrap();
+    let polygons = vec![polygon((-180.0, -90.0), (-135.0, 0.0))];
+
+    let quarter = coverage.zonal_statistics(&polygons, Membership::AreaFraction(0.2));
+    assert_eq!(quarter[0].as_ref().unwrap().sum, 5.0);
+    let half = coverage.zonal_statistics(&polygons, Membership::AreaFraction(0.3));
+    assert!(half[0].is_none());
+}
@@ -0,0 +1,66 @@
+use geo_plegmata::adapters::dggrid::common::{dggrid_metafile, parse_aigen_points};
+use geo_plegmata::models::common::ZoneQueryOptions;
+use std::fs;
+
+fn metafile_for(options: &ZoneQueryOptions, name: &str) -> String {
+    let dir = std::env::temp_dir();
+    let meta_path = dir.join(name).with_extension("meta");
+    dggrid_metafile(
+        &meta_path,
+        &4,
+        &dir.join("cells"),
+        &dir.join("children"),
+        &dir.join("neighbors"),
+        options,
+    )
+    .expect("cannot write metafile");
+    let meta = fs::read_to_string(&meta_path).expect("cannot read metafile");
+    let _ = fs::remove_file(&meta_path);
+    meta
+}
+
+#[test]
+fn test_default_options_request_everything() {
+    let meta = metafile_for(&ZoneQueryOptions::default(), "gp_default_options");
+
+    assert!(meta.contains("cell_output_type AIGEN"));
+    assert!(meta.contains("children_output_type TEXT"));
+    assert!(meta.contains("neighbor_output_type TEXT"));
+    assert!(meta.contains("precision 9"));
+    assert!(meta.contains("densification 50"));
+}
+
+#[test]
+fn test_centroid_only_skips_geometry_and_topology() {
+    let meta = metafile_for(&ZoneQueryOptions::centroid_only(), "gp_centroid_options");
+
+    assert!(meta.contains("cell_output_type NONE"));
+    assert!(meta.contains("point_output_type AIGEN"));
+    assert!(meta.contains("children_output_type NONE"));
+    assert!(meta.contains("neighbor_output_type NONE"));
+    assert!(!meta.contains("densification"));
+}
+
+#[test]
+fn test_densification_and_precision() {
+    let options = ZoneQueryOptions {
+        precision: 6,
+        ..ZoneQueryOptions::densified(10)
+    };
+    let meta = metafile_for(&options, "gp_densified_options");
+
+    assert!(meta.contains("densification 10"));
+    assert!(meta.contains("precision 6"));
+}
+
+#[test]
+fn test_parse_centroids() {
+    let data = "0010000000000000 12.5 -3.25\n0020000000000000 -170.0 45.0\nEND\n";
+    let zones = parse_aigen_points(data, &3).unwrap();
+
+    assert_eq!(zones.len(), 2);
+    assert_eq!(zones.as_slice()[0].id.to_string(), "030010000000000000");
+    assert_eq!(zones.as_slice()[1].center.x(), -170.0);
+    assert_eq!(zones.as_slice()[1].vertex_count, 0);
+    assert!(zones.as_slice()[1].children.is_empty());
+}
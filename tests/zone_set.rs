@@ -0,0 +1,83 @@
+mod common;
+
+use common::QuadGrid;
+use geo_plegmata::adapters::dggrid::igeo7::Igeo7Impl;
+use geo_plegmata::models::common::ZoneID;
+use geo_plegmata::models::zone_set::ZoneSet;
+use geo_plegmata::ports::dggrs::DggrsPort;
+
+fn set(ids: &[(u8, u32, u32)]) -> ZoneSet {
+    ZoneSet::from_ids(ids.iter().map(|&(d, x, y)| QuadGrid::id(d, x, y))).unwrap()
+}
+
+#[test]
+fn test_operations_across_depths() {
+    let grid = QuadGrid;
+    // The whole of zone (1, 0, 0) and two zones at depth 2 inside and outside of it
+    let a = set(&[(1, 0, 0)]);
+    let b = set(&[(2, 1, 1), (2, 2, 0)]);
+
+    // Zone (2, 1, 1) is covered by zone (1, 0, 0) already
+    assert_eq!(a.union(&b, &grid), set(&[(1, 0, 0), (2, 2, 0)]));
+    assert_eq!(a.intersection(&b, &grid), set(&[(2, 1, 1)]));
+    assert_eq!(a.difference(&b, &grid).len(), 3);
+    assert!(a.contains_zone(&QuadGrid::id(2, 0, 1), &grid));
+    assert!(!a.contains(&b, &grid));
+    assert!(a.union(&b, &grid).contains(&a, &grid));
+}
+
+#[test]
+fn test_results_are_compacted() {
+    let grid = QuadGrid;
+    let a = set(&[(2, 0, 0), (2, 1, 0)]);
+    let b = set(&[(2, 0, 1), (3, 2, 2), (3, 3, 2), (3, 2, 3), (3, 3, 3)]);
+
+    // The four zones of (1, 0, 0) merge back into it, the last ones in two steps
+    assert_eq!(a.union(&b, &grid), set(&[(1, 0, 0)]));
+    assert_eq!(set(&[(1, 0, 0)]).difference(&b, &grid), a);
+    assert!(set(&[(1, 0, 0)]).contains(&b, &grid));
+    assert!(
+        a.union(&b, &grid)
+            .contains_zone(&QuadGrid::id(4, 3, 3), &grid)
+    );
+}
+
+#[test]
+fn test_difference_refines_partly_covered_zones() {
+    let grid = QuadGrid;
+    let a = set(&[(1, 0, 0), (1, 1, 1)]);
+    let b = set(&[(3, 0, 0)]);
+
+    let difference = a.difference(&b, &grid);
+    // Three zones at depth 3 and at depth 2 each remain of zone (1, 0, 0)
+    assert_eq!(difference.len(), 7);
+    assert!(difference.contains_zone(&QuadGrid::id(1, 1, 1), &grid));
+    assert!(!difference.contains_zone(&QuadGrid::id(3, 0, 0), &grid));
+    assert!(a.intersection(&b, &grid) == b);
+}
+
+#[test]
+fn test_z7_ancestor() {
+    let igeo7 = Igeo7Impl::default();
+    let id = ZoneID::new("054710bfffffffffff").unwrap();
+
+    assert_eq!(
+        igeo7.zone_ancestor(&id, 3),
+        Some(ZoneID::new("034717ffffffffffff").unwrap())
+    );
+    assert_eq!(igeo7.zone_ancestor(&id, 5), Some(id.clone()));
+    assert_eq!(igeo7.zone_ancestor(&id, 6), None);
+}
+
+#[test]
+fn test_compact_form() {
+    let a = set(&[(1, 0, 0), (2, 1, 1), (2, 2, 0)]);
+    let compact = a.to_string();
+
+    assert_eq!(
+        compact,
+        "1:0000000000000000;2:0000000100000001,0000000200000000"
+    );
+    assert_eq!(compact.parse::<ZoneSet>().unwrap(), a);
+    assert!("2:00000001".parse::<ZoneSet>().is_err());
+}
@@ -0,0 +1,126 @@
+use geo_plegmata::adapters::dggrid::common::dggrid_parse;
+use geo_plegmata::adapters::dggrid::reader::{AigenReader, DggridZones};
+use geo_plegmata::models::common::{ZoneID, ZoneQueryOptions};
+use std::fs;
+
+const AIGEN: &str = "\
+00100000000000ff 10.0 20.0
+9.0 19.0
+11.0 19.0
+11.0 21.0
+9.0 21.0
+9.0 19.0
+END
+00200000000000ff 12.0 20.0
+11.0 19.0
+13.0 19.0
+13.0 21.0
+11.0 21.0
+11.0 19.0
+END
+END
+";
+
+#[test]
+fn test_aigen_reader_yields_zones_lazily() {
+    let mut reader = AigenReader::new(AIGEN.as_bytes(), 4, true);
+
+    let first = reader.next().expect("first zone").unwrap();
+    assert_eq!(first.id.to_string(), "0400100000000000ff");
+    assert_eq!(first.vertex_count, 4);
+    assert_eq!(first.center.x(), 10.0);
+
+    let second = reader.next().expect("second zone").unwrap();
+    assert_eq!(second.id.to_string(), "0400200000000000ff");
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_dggrid_zones_joins_relations_and_cleans_up() {
+    let dir = std::env::temp_dir();
+    let aigen_path = dir.join("gp_stream_test.gen");
+    let children_path = dir.join("gp_stream_test.chd");
+    let neighbor_path = dir.join("gp_stream_test.nbr");
+    fs::write(&aigen_path, AIGEN).unwrap();
+    // Relations are listed in the order of the cells, the first has no neighbours
+    fs::write(
+        &children_path,
+        "00100000000000ff 00110000000000ff\n\
+         00200000000000ff 00210000000000ff 00220000000000ff\n",
+    )
+    .unwrap();
+    fs::write(&neighbor_path, "00200000000000ff 00100000000000ff\n").unwrap();
+
+    let zones: Vec<_> = DggridZones::open(
+        &aigen_path,
+        &children_path,
+        &neighbor_path,
+        4,
+        &ZoneQueryOptions::default(),
+    )
+    .unwrap()
+    .remove_on_drop(vec![
+        aigen_path.clone(),
+        children_path.clone(),
+        neighbor_path.clone(),
+    ])
+    .collect::<Result<_, _>>()
+    .unwrap();
+
+    assert_eq!(zones.len(), 2);
+    assert_eq!(
+        zones[0].children,
+        vec![ZoneID::new("0500110000000000ff").unwrap()]
+    );
+    assert_eq!(
+        zones[1].children,
+        vec![
+            ZoneID::new("0500210000000000ff").unwrap(),
+            ZoneID::new("0500220000000000ff").unwrap()
+        ]
+    );
+    assert!(zones[0].neighbors.is_empty());
+    assert_eq!(
+        zones[1].neighbors,
+        vec![ZoneID::new("0400100000000000ff").unwrap()]
+    );
+    assert!(!aigen_path.exists());
+    assert!(!children_path.exists());
+    assert!(!neighbor_path.exists());
+}
+
+#[test]
+fn test_aigen_reader_stops_at_malformed_lines() {
+    let data = AIGEN.replace("11.0 21.0\n9.0", "11.0 north\n9.0");
+    let mut reader = AigenReader::new(data.as_bytes(), 4, true);
+
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+
+    let data = AIGEN.replace("00200000000000ff", "bad");
+    let zones: Vec<_> = AigenReader::new(data.as_bytes(), 4, true).collect();
+    assert_eq!(zones.len(), 2);
+    assert!(zones[0].is_ok() && zones[1].is_err());
+}
+
+#[test]
+fn test_dggrid_parse_reports_malformed_records() {
+    let dir = std::env::temp_dir();
+    let aigen_path = dir.join("gp_parse_test.gen");
+    let missing = dir.join("gp_parse_test.missing");
+    fs::write(&aigen_path, AIGEN.replace("00200000000000ff", "bad")).unwrap();
+    let options = ZoneQueryOptions {
+        children: false,
+        neighbors: false,
+        ..ZoneQueryOptions::default()
+    };
+
+    let zones = DggridZones::open(&aigen_path, &missing, &missing, 4, &options).unwrap();
+    let zones: Vec<_> = zones.remove_on_drop(vec![]).collect();
+    assert_eq!(zones.len(), 2);
+    assert!(zones[0].is_ok() && zones[1].is_err());
+
+    assert!(dggrid_parse(&aigen_path, &missing, &missing, &4, &options).is_err());
+    fs::remove_file(&aigen_path).unwrap();
+    assert!(dggrid_parse(&aigen_path, &missing, &missing, &4, &options).is_err());
+}
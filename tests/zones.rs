@@ -0,0 +1,79 @@
+use geo::{LineString, Point, Polygon};
+use geo_plegmata::models::common::{Zone, ZoneID, Zones};
+
+fn zone(id: &str, x: f64) -> Zone {
+    Zone {
+        id: ZoneID::new(id).unwrap(),
+        region: Polygon::new(LineString::new(vec![]), vec![]),
+        center: Point::new(x, 0.0),
+        vertex_count: 0,
+        children: vec![],
+        neighbors: vec![],
+    }
+}
+
+#[test]
+fn test_zone_id_ordering_and_hashing() {
+    let a = ZoneID::new("050000000000000001").unwrap();
+    let b = ZoneID::new("050000000000000002").unwrap();
+
+    assert!(a < b);
+    assert_eq!(a, a.clone());
+    let set: std::collections::HashSet<_> = [a.clone(), b, a].into_iter().collect();
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_get_and_contains() {
+    let zones: Zones = vec![
+        zone("050000000000000001", 1.0),
+        zone("050000000000000002", 2.0),
+    ]
+    .into();
+    let id = ZoneID::new("050000000000000002").unwrap();
+
+    assert_eq!(zones.len(), 2);
+    assert!(zones.contains(&id));
+    assert_eq!(zones.get(&id).unwrap().center.x(), 2.0);
+    assert!(
+        zones
+            .get(&ZoneID::new("050000000000000003").unwrap())
+            .is_none()
+    );
+}
+
+#[test]
+fn test_merge_keeps_existing_zones() {
+    let mut zones: Zones = vec![zone("050000000000000001", 1.0)].into();
+    let other: Zones = vec![
+        zone("050000000000000001", 10.0),
+        zone("050000000000000002", 2.0),
+    ]
+    .into();
+
+    zones.merge(other);
+
+    assert_eq!(zones.len(), 2);
+    let xs: Vec<f64> = zones.iter().map(|z| z.center.x()).collect();
+    assert_eq!(xs, vec![1.0, 2.0]);
+}
+
+#[test]
+fn test_dedup_keeps_first_zone() {
+    let mut zones: Zones = [
+        zone("050000000000000001", 1.0),
+        zone("050000000000000002", 2.0),
+        zone("050000000000000001", 3.0),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(zones.len(), 3);
+
+    zones.dedup();
+
+    assert_eq!(zones.len(), 2);
+    let ids: Vec<String> = zones.ids().map(|id| id.to_string()).collect();
+    assert_eq!(ids, vec!["050000000000000001", "050000000000000002"]);
+    let total: f64 = (&zones).into_iter().map(|z| z.center.x()).sum();
+    assert_eq!(total, 3.0);
+}